@@ -0,0 +1,93 @@
+//! Copies the input of every day to `OUT_DIR/dayXX/input.txt`, where `inputs/mod.rs` embeds it
+//! from. The input is `input.txt`, or else `input.txt.enc` decrypted with the input key. Inputs
+//! that are missing or can't be decrypted are empty, so a clean checkout still compiles. The days
+//! with an encrypted input that couldn't be decrypted are passed on in `AOC_LOCKED_INPUTS`, and the
+//! days without either input file in `AOC_MISSING_INPUTS`.
+//!
+//! The git commit of the build, if any, is passed on in `AOC_GIT_COMMIT` for the run history.
+
+use std::{env, fs, path::PathBuf, process::Command};
+
+use anyhow::Result;
+
+// Only decrypts
+#[allow(dead_code)]
+#[path = "src/encrypt/cipher.rs"]
+mod cipher;
+
+fn main() -> Result<()> {
+    for var in [cipher::KEY_VAR, cipher::KEY_FILE_VAR, "HOME"] {
+        println!("cargo:rerun-if-env-changed={var}");
+    }
+    // Watch the directory while the key file doesn't exist, to notice it being created
+    if let Some(path) = cipher::key_file() {
+        if let Some(watched) = path.ancestors().find(|path| path.exists()) {
+            println!("cargo:rerun-if-changed={}", watched.display());
+        }
+    }
+
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    let passphrase = cipher::passphrase()?;
+    let mut locked = Vec::new();
+    let mut missing = Vec::new();
+
+    let mut days: Vec<_> = fs::read_dir("src/days")?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join("inputs").is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    days.sort();
+
+    for day in days {
+        let inputs = PathBuf::from("src/days").join(&day).join("inputs");
+        println!("cargo:rerun-if-changed={}", inputs.display());
+
+        let plain = inputs.join("input.txt");
+        let encrypted = inputs.join("input.txt.enc");
+        let input = if plain.exists() {
+            fs::read(&plain)?
+        } else if encrypted.exists() {
+            let decrypted = match &passphrase {
+                Some(passphrase) => cipher::decrypt(passphrase, &fs::read(&encrypted)?),
+                None => Err(anyhow::anyhow!("no input key")),
+            };
+            decrypted.unwrap_or_else(|error| {
+                println!(
+                    "cargo:warning=Cannot decrypt {}: {error}",
+                    encrypted.display()
+                );
+                locked.push(day.trim_start_matches("day").to_string());
+                Vec::new()
+            })
+        } else {
+            missing.push(day.trim_start_matches("day").to_string());
+            Vec::new()
+        };
+
+        // Rewriting an unchanged input would recompile the crate
+        let out = out_dir.join(&day).join("input.txt");
+        if fs::read(&out).ok().as_ref() != Some(&input) {
+            fs::create_dir_all(out_dir.join(&day))?;
+            fs::write(out, input)?;
+        }
+    }
+
+    println!("cargo:rustc-env=AOC_LOCKED_INPUTS={}", locked.join(","));
+    println!("cargo:rustc-env=AOC_MISSING_INPUTS={}", missing.join(","));
+
+    for path in [".git/HEAD", ".git/refs/heads", ".git/packed-refs"] {
+        if PathBuf::from(path).exists() {
+            println!("cargo:rerun-if-changed={path}");
+        }
+    }
+    let commit = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+    println!("cargo:rustc-env=AOC_GIT_COMMIT={commit}");
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{Part, Result};
+
+/// A single puzzle input from a corpus directory laid out as `<corpus>/<name>/dayXX.txt`.
+///
+/// Expected answers can be put next to the input in `<corpus>/<name>/dayXX.answers`: the first line
+/// is the answer of part 1, the second line the answer of part 2. Empty lines are ignored.
+pub struct Entry {
+    pub name: String,
+    pub day: u8,
+    pub path: PathBuf,
+    /// The input, or why it can't be read, e.g. because it isn't UTF-8
+    pub input: std::result::Result<&'static str, String>,
+    pub expected: [Option<String>; 2],
+}
+
+impl Entry {
+    pub fn expected(&self, part: Part) -> Option<&str> {
+        self.expected[u8::from(part) as usize - 1].as_deref()
+    }
+}
+
+/// Load all inputs of a corpus, sorted by name and day. Inputs are leaked, because days need a
+/// `&'static str`. An input that can't be read is an entry with an error, so the rest of the
+/// corpus still runs.
+pub fn load_corpus(corpus: &Path) -> Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+
+    for name_dir in fs::read_dir(corpus)? {
+        let name_dir = name_dir?.path();
+        if !name_dir.is_dir() {
+            continue;
+        }
+        let name = name_dir
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+
+        for file in fs::read_dir(&name_dir)? {
+            let path = file?.path();
+            let Some(day) = path
+                .file_name()
+                .and_then(|file_name| file_name.to_str())
+                .and_then(|file_name| file_name.strip_prefix("day"))
+                .and_then(|file_name| file_name.strip_suffix(".txt"))
+                .and_then(|day| day.parse().ok())
+            else {
+                continue;
+            };
+
+            let input = fs::read_to_string(&path)
+                .map(|input| &*input.leak())
+                .map_err(|error| format!("Cannot read {}: {error}", path.display()));
+
+            let mut expected = [None, None];
+            if let Ok(answers) = fs::read_to_string(path.with_extension("answers")) {
+                for (answer, line) in expected.iter_mut().zip(answers.lines()) {
+                    let line = line.trim();
+                    if !line.is_empty() {
+                        *answer = Some(line.to_string());
+                    }
+                }
+            }
+
+            entries.push(Entry {
+                name: name.clone(),
+                day,
+                path,
+                input,
+                expected,
+            });
+        }
+    }
+
+    entries.sort_by(|a, b| a.name.cmp(&b.name).then(a.day.cmp(&b.day)));
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+#[test]
+fn unreadable_inputs_are_entries_with_an_error() {
+    let corpus = tempfile::tempdir().unwrap();
+    let alice = corpus.path().join("alice");
+    let bob = corpus.path().join("bob");
+    fs::create_dir_all(&alice).unwrap();
+    fs::create_dir_all(&bob).unwrap();
+    fs::write(alice.join("day02.txt"), "2").unwrap();
+    fs::write(alice.join("day02.answers"), "\n8\n").unwrap();
+    fs::write(alice.join("day01.txt"), "1").unwrap();
+    fs::write(alice.join("notes.md"), "Not an input").unwrap();
+    fs::write(bob.join("day01.txt"), [0xff, 0xfe]).unwrap();
+    fs::write(corpus.path().join("day01.txt"), "Not in a directory").unwrap();
+
+    let entries = load_corpus(corpus.path()).unwrap();
+    let keys: Vec<_> = entries
+        .iter()
+        .map(|entry| (entry.name.as_str(), entry.day))
+        .collect();
+    assert_eq!(keys, [("alice", 1), ("alice", 2), ("bob", 1)]);
+
+    assert_eq!(entries[0].input, Ok("1"));
+    assert_eq!(entries[0].expected, [None, None]);
+    assert_eq!(entries[1].expected(Part::Part1), None);
+    assert_eq!(entries[1].expected(Part::Part2), Some("8"));
+    let error = entries[2].input.as_ref().unwrap_err();
+    assert!(error.contains("bob"), "{error}");
+}
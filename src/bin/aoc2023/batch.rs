@@ -0,0 +1,169 @@
+use std::{collections::BTreeSet, path::Path, time::Instant};
+
+use aoc2023::{batch::load_corpus, prelude::*};
+use serde::Serialize;
+
+use super::{Runner, Source};
+
+#[derive(Serialize)]
+struct Cell {
+    name: String,
+    day: u8,
+    part: u8,
+    #[serde(flatten)]
+    outcome: Outcome,
+    elapsed_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    agrees: Option<bool>,
+}
+
+pub fn run(runner: &Runner, corpus: &Path) -> Result<()> {
+    let cli = runner.cli;
+    let entries = load_corpus(corpus)?;
+    let mut cells = Vec::new();
+
+    for entry in entries {
+        if !cli.day.is_empty() && !cli.day.contains(&entry.day) {
+            continue;
+        }
+
+        for part in [Part1, Part2] {
+            if cli.part.is_some_and(|only| only != u8::from(part)) {
+                continue;
+            }
+
+            let start = Instant::now();
+            let outcome = entry
+                .input
+                .clone()
+                .map_err(anyhow::Error::msg)
+                .and_then(|input| {
+                    let source = Source::File {
+                        path: entry.path.clone(),
+                        input,
+                    };
+                    runner.run_part(entry.day, part, &source)
+                })
+                .unwrap_or_else(|error| Outcome::Error {
+                    message: format!("{error:#}"),
+                });
+            let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+            let expected = entry.expected(part).map(str::to_string);
+            let agrees = expected.as_ref().map(
+                |expected| matches!(&outcome, Outcome::Answer { output } if output == expected),
+            );
+
+            cells.push(Cell {
+                name: entry.name.clone(),
+                day: entry.day,
+                part: part.into(),
+                outcome,
+                elapsed_ms,
+                expected,
+                agrees,
+            });
+        }
+    }
+
+    if cli.format.is_some() {
+        println!("{}", serde_json::to_string_pretty(&cells)?);
+    } else {
+        print_matrix(&cells);
+    }
+
+    Ok(())
+}
+
+fn print_matrix(cells: &[Cell]) {
+    let names: BTreeSet<_> = cells.iter().map(|cell| cell.name.as_str()).collect();
+    let rows: BTreeSet<_> = cells.iter().map(|cell| (cell.day, cell.part)).collect();
+
+    let text = |cell: &Cell| {
+        let mark = match cell.agrees {
+            Some(true) => " ✓",
+            Some(false) => " ✗",
+            None => "",
+        };
+        let outcome = match &cell.outcome {
+            Outcome::Answer { output } => output.clone(),
+            Outcome::Error { .. } => "error".to_string(),
+            Outcome::Timeout { .. } => "timeout".to_string(),
+            Outcome::OutOfMemory { .. } => "oom".to_string(),
+            Outcome::Crashed { .. } => "crash".to_string(),
+        };
+        format!("{outcome} ({:.1} ms){mark}", cell.elapsed_ms)
+    };
+    let find = |name: &str, (day, part): (u8, u8)| {
+        cells
+            .iter()
+            .find(|cell| cell.name == name && cell.day == day && cell.part == part)
+    };
+
+    let widths: Vec<_> = names
+        .iter()
+        .map(|name| {
+            cells
+                .iter()
+                .filter(|cell| cell.name == *name)
+                .map(|cell| text(cell).chars().count())
+                .chain([name.len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    print!("Day Part");
+    for (name, width) in names.iter().zip(&widths) {
+        print!(" | {name:width$}");
+    }
+    println!();
+
+    for row in rows {
+        print!(" {:02}    {}", row.0, row.1);
+        for (name, width) in names.iter().zip(&widths) {
+            let cell = find(name, row).map(text).unwrap_or_default();
+            print!(" | {cell:width$}");
+        }
+        println!();
+    }
+
+    let checked = cells.iter().filter(|cell| cell.agrees.is_some()).count();
+    let disagreements: Vec<_> = cells
+        .iter()
+        .filter(|cell| cell.agrees == Some(false))
+        .collect();
+    if checked > 0 {
+        println!();
+        println!(
+            "{} of {checked} answers agree",
+            checked - disagreements.len()
+        );
+        for cell in disagreements {
+            println!(
+                "  {} day {:02} part {}: expected {}, got {}",
+                cell.name,
+                cell.day,
+                cell.part,
+                cell.expected.as_deref().unwrap_or_default(),
+                cell.outcome
+            );
+        }
+    }
+
+    let errors: Vec<_> = cells
+        .iter()
+        .filter(|cell| !matches!(cell.outcome, Outcome::Answer { .. }))
+        .collect();
+    if !errors.is_empty() {
+        println!();
+        for cell in errors {
+            println!(
+                "  {} day {:02} part {}: {}",
+                cell.name, cell.day, cell.part, cell.outcome
+            );
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use std::{
+    fs,
+    io::{self, BufRead, Write},
+    path::Path,
+};
+
+use aoc2023::{
+    extract::{inputs_module, set_expected, Assignment, Puzzle},
+    prelude::*,
+    watch::{day_dir, input_path},
+};
+
+use super::Cli;
+
+/// Extract the examples of a saved puzzle page into the source tree, after the user confirmed or
+/// corrected which example block and answer belong to which part.
+pub fn run(cli: &Cli, page: &Path, yes: bool) -> Result<()> {
+    let html = fs::read_to_string(page)
+        .with_context(|| format!("Cannot read puzzle page {}", page.display()))?;
+    let puzzle = Puzzle::parse(&html)?;
+    let day = match (&cli.day[..], puzzle.day) {
+        (&[day], _) | ([], Some(day)) => day,
+        ([], None) => bail!("No day found in the page, use --day"),
+        _ => bail!("Extract needs a single --day"),
+    };
+
+    let blocks = puzzle.blocks();
+    for (index, block) in blocks.iter().enumerate() {
+        println!("{}", paint(format!("Block {}:", index + 1), Color::Gray));
+        for line in block.lines() {
+            println!("    {line}");
+        }
+    }
+
+    let mut assignments = Vec::new();
+    for suggestion in puzzle.suggest() {
+        let assignment = if yes {
+            Some(suggestion)
+        } else {
+            confirm(suggestion, blocks.len())?
+        };
+        assignments.extend(assignment);
+    }
+
+    let mut examples = existing_examples(day)?;
+    let mut days = fs::read_to_string(days_path())?;
+    for assignment in &assignments {
+        let block = blocks[assignment.block];
+        let example = match examples.iter().position(|existing| existing == block) {
+            Some(index) => index + 1,
+            None => {
+                examples.push(block.to_string());
+                let example = examples.len();
+                fs::write(input_path(day, example), block)?;
+                println!("Saved example {example}");
+                example
+            }
+        };
+        days = set_expected(&days, day, example, assignment.part, &assignment.expected)?;
+        println!(
+            "Day {day:02} example {example} part {} expected {}",
+            assignment.part, assignment.expected
+        );
+    }
+
+    fs::write(days_path(), days)?;
+    fs::write(
+        day_dir(day).join("inputs").join("mod.rs"),
+        inputs_module(day, examples.len()),
+    )?;
+    Ok(())
+}
+
+/// Ask whether the suggested block and answer are right, returning the confirmed assignment, or
+/// none if the part is skipped.
+fn confirm(suggestion: Assignment, blocks: usize) -> Result<Option<Assignment>> {
+    let mut stdin = io::stdin().lock();
+    let ask = |question: String, stdin: &mut io::StdinLock| -> Result<String> {
+        print!("{question} ");
+        io::stdout().flush()?;
+        let mut line = String::new();
+        stdin.read_line(&mut line)?;
+        Ok(line.trim().to_string())
+    };
+
+    let block = loop {
+        let answer = ask(
+            format!(
+                "Part {} example block [{}] (number, or 0 to skip):",
+                suggestion.part,
+                suggestion.block + 1
+            ),
+            &mut stdin,
+        )?;
+        match answer.parse::<usize>() {
+            _ if answer.is_empty() => break suggestion.block,
+            Ok(0) => return Ok(None),
+            Ok(block) if block <= blocks => break block - 1,
+            _ => println!("Enter a block number from 1 to {blocks}"),
+        }
+    };
+
+    let expected = ask(
+        format!(
+            "Part {} expected answer [{}]:",
+            suggestion.part, suggestion.expected
+        ),
+        &mut stdin,
+    )?;
+    Ok(Some(Assignment {
+        block,
+        expected: match expected.is_empty() {
+            true => suggestion.expected,
+            false => expected,
+        },
+        ..suggestion
+    }))
+}
+
+/// The contents of `example1.txt`, `example2.txt` and so on of a day, up to the first missing one.
+fn existing_examples(day: u8) -> Result<Vec<String>> {
+    let mut examples = Vec::new();
+    while let Ok(example) = fs::read_to_string(input_path(day, examples.len() + 1)) {
+        examples.push(example.trim_end_matches('\n').to_string());
+    }
+    Ok(examples)
+}
+
+fn days_path() -> std::path::PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("days.rs")
+}
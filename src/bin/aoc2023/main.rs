@@ -0,0 +1,1208 @@
+use aoc2023::{
+    cache::Cache,
+    config::{self, Config, OutputFormat},
+    encrypt,
+    fetch::{self, Fetcher},
+    history::{self, History, Run},
+    prelude::*,
+    submit::{Guess, GuessLog, Verdict},
+    trace::{self, TraceFormat},
+    variant,
+};
+
+use std::{
+    cell::RefCell,
+    env, fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use chrono::{Datelike, Local};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+
+mod batch;
+mod extract;
+mod scale;
+mod watch;
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Output format. If ommitted, you can only run one day/example. If no day is specified, today is run.
+    #[arg(short, long, global = true)]
+    format: Option<Format>,
+
+    /// Read defaults from this file, instead of the user and project `aoc.toml` files.
+    #[arg(long, global = true, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Print the effective settings of the configuration files and command line, instead of running.
+    #[arg(long)]
+    print_config: bool,
+
+    /// Read the input of a day from `<DIR>/dayXX.txt` if it exists, instead of the embedded input.
+    #[arg(long, global = true, value_name = "DIR")]
+    input_dir: Option<PathBuf>,
+
+    /// Day to run. Repeat for more days. If this and --today are omitted, all days are run.
+    #[arg(short, long, global = true, num_args = 0.., value_delimiter = ',')]
+    day: Vec<u8>,
+
+    /// Run today
+    #[arg(long)]
+    today: bool,
+
+    /// Run a single example. Repeat to run more examples.
+    #[arg(short, long, global = true, value_parser, num_args = 0.., value_delimiter = ',')]
+    example: Option<Vec<usize>>,
+
+    /// Read the puzzle input from a file instead of the embedded input. Only the first provided day is run.
+    #[arg(short, long, global = true, conflicts_with = "example")]
+    input: Option<PathBuf>,
+
+    /// Run only one part. If both parts are run, the parsed input is reused if possible.
+    #[arg(short, long, global = true, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Maximum number of seconds per part. Parts run on a worker thread and are cancelled when they time out.
+    #[arg(short, long, global = true, value_parser = seconds)]
+    timeout: Option<f64>,
+
+    /// Run parts until they finish, also if a configuration file sets a timeout.
+    #[arg(long, global = true, conflicts_with = "timeout")]
+    no_timeout: bool,
+
+    /// Run every day and part in a separate child process with memory and CPU limits (Linux only).
+    #[arg(long, global = true)]
+    isolate: bool,
+
+    /// Maximum address space in MiB of an isolated child process, at most 1 TiB.
+    #[arg(long, global = true, default_value_t = 1024, requires = "isolate", value_parser = clap::value_parser!(u64).range(1..=1024 * 1024))]
+    memory_limit: u64,
+
+    /// Maximum CPU time in seconds of an isolated child process, at most a day.
+    #[arg(long, global = true, default_value_t = 60, requires = "isolate", value_parser = clap::value_parser!(u64).range(1..=24 * 60 * 60))]
+    cpu_limit: u64,
+
+    /// Solve with the named solver variant of the selected days instead of the default solution. Parts
+    /// without the variant use the default solution. Answers of variants are never cached.
+    #[arg(long, global = true)]
+    variant: Option<String>,
+
+    /// Always compute answers, instead of returning answers cached by an earlier run of the same build.
+    #[arg(long, global = true)]
+    no_cache: bool,
+
+    /// File with the cached answers. Defaults to `aoc2023-cache.json` next to the executable.
+    #[arg(long, global = true, value_name = "FILE")]
+    cache_file: Option<PathBuf>,
+
+    /// Don't record the answers and timings of this run in the history.
+    #[arg(long, global = true)]
+    no_history: bool,
+
+    /// File with the history of runs. Defaults to `~/.local/share/aoc/2023-history.jsonl`.
+    #[arg(long, global = true, value_name = "FILE")]
+    history_file: Option<PathBuf>,
+
+    /// Number of threads of solutions that run in parallel. Defaults to the number of CPUs.
+    #[arg(short, long, global = true)]
+    jobs: Option<usize>,
+
+    /// File with the session token of fetch and submit, if `AOC_SESSION` isn't set. Defaults to the
+    /// file in `AOC_SESSION_FILE`, then `session-file` of `aoc.toml`, then `~/.config/aoc/session`.
+    #[arg(long, global = true, value_name = "FILE")]
+    session_file: Option<PathBuf>,
+
+    /// Print the parsed input of the selected days as JSON, instead of running them.
+    #[arg(long)]
+    dump_parsed: bool,
+
+    /// Show the parsed or solved state of the selected days in the terminal, instead of running them.
+    #[arg(long, conflicts_with = "dump_parsed")]
+    visualize: bool,
+
+    /// Draw the parsed or solved state of a single day as an SVG or PNG image, instead of running it.
+    /// Without --part, both parts are drawn, to files with `-part1` and `-part2` added to the name.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["dump_parsed", "visualize"])]
+    render: Option<PathBuf>,
+
+    /// Trace parsing and solving to stderr. The filter is a level (error, warn, info, debug or trace),
+    /// or directives like `aoc2023::days::day20=trace`. Defaults to debug.
+    #[arg(long, global = true, value_name = "FILTER", num_args = 0..=1, default_missing_value = "debug", conflicts_with = "isolate")]
+    trace: Option<String>,
+
+    /// Format of the trace output.
+    #[arg(long, global = true, value_enum, default_value_t = TraceOutput::Text, requires = "trace")]
+    trace_format: TraceOutput,
+
+    /// Used by --isolate: run a single day and part, and write the outcome as JSON to stdout.
+    #[arg(long, hide = true)]
+    isolated: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run every day on every input of a corpus laid out as `<CORPUS>/<name>/dayXX.txt`, and compare
+    /// with the answers in `<CORPUS>/<name>/dayXX.answers` if present.
+    Batch {
+        /// Corpus directory
+        corpus: PathBuf,
+    },
+    /// Check the assumptions the solutions make about the input of the selected days, or all days.
+    Validate,
+    /// Print the fingerprint of the input of the selected days, or of the file given with --input.
+    Fingerprint,
+    /// Time the selected days, or all days with an input generator, on generated inputs of doubling
+    /// sizes, and estimate the exponent of their complexity.
+    Scale {
+        /// Smallest input size
+        #[arg(long, default_value_t = 2)]
+        min_size: usize,
+        /// Largest input size
+        #[arg(long, default_value_t = 4096)]
+        max_size: usize,
+        /// Stop growing the input of a part once it takes longer than this number of seconds.
+        #[arg(long, default_value_t = 2.0, value_parser = positive_seconds)]
+        budget: f64,
+        /// Number of runs per size, of which the fastest is used.
+        #[arg(long, default_value_t = 3)]
+        repeat: usize,
+    },
+    /// Run the selected days, or today, and run them again whenever their sources or inputs in
+    /// `src/days/dayXX` change. Inputs are read from the source tree, and answers are compared with
+    /// the previous run and with the expected answers of examples.
+    Watch {
+        /// Number of seconds between checks for changes.
+        #[arg(long, default_value_t = 0.5, value_parser = positive_seconds)]
+        interval: f64,
+    },
+    /// Download the input of the selected days, or today, to `src/days/dayXX/inputs/input.txt`. The
+    /// session token is read from `AOC_SESSION`, or from the file of --session-file. Downloads are
+    /// cached, so every input is requested only once.
+    Fetch {
+        /// Server to download from.
+        #[arg(long, env = "AOC_BASE_URL", default_value = fetch::DEFAULT_BASE_URL)]
+        base_url: String,
+        /// Write the inputs to `<DIR>/dayXX.txt` instead, e.g. a corpus directory for batch mode.
+        #[arg(long, value_name = "DIR")]
+        output_dir: Option<PathBuf>,
+        /// Overwrite existing input files.
+        #[arg(long)]
+        force: bool,
+        /// Minimum number of seconds between requests.
+        #[arg(long, default_value_t = 5.0, value_parser = seconds)]
+        rate_limit: f64,
+    },
+    /// Run a part of a day and submit the answer. Needs a single --day and --part. Answers known to be
+    /// wrong from earlier submissions are refused, and every submission is logged.
+    Submit {
+        /// Server to submit to.
+        #[arg(long, env = "AOC_BASE_URL", default_value = fetch::DEFAULT_BASE_URL)]
+        base_url: String,
+    },
+    /// Extract the examples and their expected answers from a saved puzzle page, asking which example
+    /// belongs to which part. Writes `src/days/dayXX/inputs/exampleN.txt` and the expected answers in
+    /// `src/days.rs`. The day is taken from the page, unless given with --day.
+    Extract {
+        /// Saved puzzle page (HTML)
+        page: PathBuf,
+        /// Accept the suggested examples and answers without asking.
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Show how the time of every part of the selected days evolved over the recorded runs, and when
+    /// answers changed.
+    History,
+    /// Encrypt `input.txt` of the selected days, or all days with an input, to `input.txt.enc`, so it
+    /// can be committed. The passphrase is read from `AOC_INPUT_KEY`, or from the file in
+    /// `AOC_INPUT_KEY_FILE`, by default `~/.config/aoc/input-key`.
+    Encrypt,
+}
+
+/// Where the input of a day comes from.
+enum Source {
+    /// Embedded input (0) or example
+    Embedded(usize),
+    /// Input read from a file
+    File { path: PathBuf, input: &'static str },
+    /// Input generated by the day
+    Generated { input: &'static str },
+}
+
+impl Source {
+    fn file(path: &Path) -> Result<Source> {
+        let input = fs::read_to_string(path)
+            .with_context(|| format!("Cannot read input file {}", path.display()))?
+            .leak();
+        Ok(Source::File {
+            path: path.to_path_buf(),
+            input,
+        })
+    }
+
+    /// The input of a day: `dayXX.txt` in the input directory if it exists, or else the embedded
+    /// input.
+    fn of(cli: &Cli, day: u8) -> Result<Source> {
+        match &cli.input_dir {
+            Some(dir) if dir.join(format!("day{day:02}.txt")).is_file() => {
+                Source::file(&dir.join(format!("day{day:02}.txt")))
+            }
+            _ => Ok(Source::Embedded(0)),
+        }
+    }
+
+    fn input(&self, day: u8) -> Result<&'static str> {
+        match self {
+            Source::Embedded(example) => get_input(day, *example),
+            Source::File { input, .. } | Source::Generated { input } => Ok(input),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum Format {
+    /// Output results as text
+    Text,
+    /// Output results in JSON format
+    Json,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum TraceOutput {
+    /// Human readable lines
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+impl From<TraceOutput> for TraceFormat {
+    fn from(output: TraceOutput) -> Self {
+        match output {
+            TraceOutput::Text => TraceFormat::Text,
+            TraceOutput::Json => TraceFormat::Json,
+        }
+    }
+}
+
+/// Parse a number of seconds that fits a `Duration`: not negative, NaN or infinite.
+fn seconds(arg: &str) -> Result<f64, String> {
+    let seconds: f64 = arg.parse().map_err(|error| format!("{error}"))?;
+    Duration::try_from_secs_f64(seconds)
+        .map_err(|_| "expected a finite, non-negative number of seconds".to_string())?;
+    Ok(seconds)
+}
+
+/// Like [`seconds`], but also not zero, for durations that are waited for or divided into.
+fn positive_seconds(arg: &str) -> Result<f64, String> {
+    match seconds(arg)? {
+        0.0 => Err("expected a positive number of seconds".to_string()),
+        seconds => Ok(seconds),
+    }
+}
+
+fn main() -> Result<()> {
+    let mut cli = Cli::parse();
+    let (config, files) = Config::load(cli.config.as_deref())?;
+    configure(&mut cli, config);
+    if cli.print_config {
+        return print_config(&cli, &files);
+    }
+    if let Some(jobs) = cli.jobs {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build_global()?;
+    }
+
+    if let Some(filter) = &cli.trace {
+        trace::init(filter, cli.trace_format.into())?;
+    }
+
+    if cli.isolated {
+        return run_isolated_child(&cli);
+    }
+
+    if cli.dump_parsed {
+        return print_parsed(&cli);
+    }
+    if cli.visualize {
+        return print_visualizations(&cli);
+    }
+    if let Some(path) = &cli.render {
+        return render(&cli, path);
+    }
+
+    let runner = Runner::new(&cli)?;
+
+    match &cli.command {
+        Some(Command::Batch { corpus }) => {
+            // Cached answers would be timed as taking no time at all
+            let runner = Runner {
+                cache: None,
+                ..runner
+            };
+            return batch::run(&runner, corpus);
+        }
+        Some(Command::Validate) => return validate(&cli),
+        Some(Command::Fingerprint) => return print_fingerprints(&cli),
+        Some(Command::Scale {
+            min_size,
+            max_size,
+            budget,
+            repeat,
+        }) => {
+            let sizes = scale::Sizes {
+                min: *min_size,
+                max: *max_size,
+                budget: *budget,
+                repeat: *repeat,
+            };
+            return scale::run(&Runner::uncached(&cli), &sizes);
+        }
+        Some(Command::Watch { interval }) => {
+            let days = if cli.day.is_empty() {
+                vec![Local::now().day() as u8]
+            } else {
+                cli.day.clone()
+            };
+            let interval = Duration::from_secs_f64(*interval);
+            return watch::run(&Runner::uncached(&cli), &days, interval);
+        }
+        Some(Command::Fetch {
+            base_url,
+            output_dir,
+            force,
+            rate_limit,
+        }) => {
+            let fetcher = Fetcher {
+                base_url: base_url.clone(),
+                session: fetch::session(cli.session_file.as_deref())?,
+                cache_dir: Fetcher::default_cache_dir()?,
+                rate_limit: Duration::from_secs_f64(*rate_limit),
+            };
+            let output_dir = output_dir.as_deref().or(cli.input_dir.as_deref());
+            return fetch_inputs(&cli, &fetcher, output_dir, *force);
+        }
+        Some(Command::Submit { base_url }) => {
+            let fetcher = Fetcher {
+                base_url: base_url.clone(),
+                session: fetch::session(cli.session_file.as_deref())?,
+                cache_dir: Fetcher::default_cache_dir()?,
+                rate_limit: Duration::from_secs(5),
+            };
+            submit(&runner, &fetcher)?;
+            return runner.save();
+        }
+        Some(Command::Extract { page, yes }) => return extract::run(&cli, page, *yes),
+        Some(Command::Encrypt) => return encrypt_inputs(&cli),
+        Some(Command::History) => return print_history(&cli),
+        None => {}
+    }
+
+    let mut outputs = Vec::new();
+
+    let days: Vec<_> = if cli.format.is_some() {
+        if cli.today {
+            DAYS.iter()
+                .filter(|d| d.day == Local::now().day() as u8)
+                .collect()
+        } else if cli.day.is_empty() {
+            DAYS.iter().collect()
+        } else {
+            DAYS.iter().filter(|d| cli.day.contains(&d.day)).collect()
+        }
+    } else if cli.today || (cli.day.is_empty() && cli.input.is_none()) {
+        DAYS.iter()
+            .filter(|d| d.day == Local::now().day() as u8)
+            .collect()
+    } else {
+        DAYS.iter().filter(|d| d.day == cli.day[0]).collect()
+    };
+
+    for day in days {
+        if let Some(examples) = &cli.example {
+            let examples: Vec<_> = if cli.format.is_none() {
+                if examples.is_empty() {
+                    day.examples.iter().take(1).collect()
+                } else {
+                    day.examples
+                        .iter()
+                        .filter(|example| example.example == examples[0])
+                        .collect()
+                }
+            } else if examples.is_empty() {
+                day.examples.iter().collect()
+            } else {
+                day.examples
+                    .iter()
+                    .filter(|example| examples.contains(&example.example))
+                    .collect()
+            };
+
+            for example in examples {
+                outputs.push((
+                    day.day,
+                    example.example,
+                    runner.run_reported(day.day, &Source::Embedded(example.example))?,
+                ));
+            }
+        } else if let Some(path) = &cli.input {
+            outputs.push((
+                day.day,
+                0,
+                runner.run_reported(day.day, &Source::file(path)?)?,
+            ));
+        } else {
+            outputs.push((
+                day.day,
+                0,
+                runner.run_reported(day.day, &Source::of(&cli, day.day)?)?,
+            ));
+        }
+    }
+
+    if cli.format.is_none() {
+        for (_, _, (part1, part2)) in outputs {
+            if let Some(outcome) = part1 {
+                println!("{outcome}");
+            }
+            if let Some(outcome) = part2 {
+                println!("{outcome}");
+            }
+        }
+    } else {
+        let json = serde_json::to_string_pretty(
+            &outputs
+                .into_iter()
+                .flat_map(|(day, example, (part1, part2))| {
+                    let example = if example == 0 { None } else { Some(example) };
+                    [(1, part1), (2, part2)]
+                        .into_iter()
+                        .filter_map(move |(part, outcome)| {
+                            outcome.map(|outcome| JsonOutput {
+                                day,
+                                example,
+                                part,
+                                output: match outcome {
+                                    Outcome::Answer { .. } => None,
+                                    _ => Some(outcome.to_string()),
+                                },
+                                outcome,
+                            })
+                        })
+                })
+                .collect::<Vec<_>>(),
+        )?;
+
+        println!("{json}");
+    }
+
+    runner.save()
+}
+
+#[derive(Serialize)]
+struct JsonOutput {
+    day: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    example: Option<usize>,
+    part: u8,
+    /// Every entry has an `output`, like before `status` was added: the answer is in `outcome`,
+    /// other outcomes are described here
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output: Option<String>,
+    #[serde(flatten)]
+    outcome: Outcome,
+}
+
+/// The inputs of the selected days: the file given with --input, the selected examples or the
+/// embedded inputs.
+fn inputs(cli: &Cli) -> Result<Vec<(u8, usize, Source)>> {
+    let mut inputs = Vec::new();
+    for day in DAYS
+        .iter()
+        .filter(|day| cli.day.is_empty() || cli.day.contains(&day.day))
+    {
+        if let Some(path) = &cli.input {
+            inputs.push((day.day, 0, Source::file(path)?));
+        } else if let Some(examples) = &cli.example {
+            for example in day
+                .examples
+                .iter()
+                .filter(|example| examples.is_empty() || examples.contains(&example.example))
+            {
+                inputs.push((day.day, example.example, Source::Embedded(example.example)));
+            }
+        } else {
+            inputs.push((day.day, 0, Source::of(cli, day.day)?));
+        }
+    }
+    Ok(inputs)
+}
+
+fn validate(cli: &Cli) -> Result<()> {
+    let mut outputs = Vec::new();
+    for (day, example, source) in inputs(cli)? {
+        let violations = validate_day(day, source.input(day)?)?;
+        outputs.push((day, example, violations));
+    }
+    let violated = outputs
+        .iter()
+        .map(|(_, _, violations)| violations.len())
+        .sum::<usize>();
+
+    if cli.format.is_none() {
+        for (day, example, violations) in &outputs {
+            let name = match example {
+                0 => format!("Day {day:02}"),
+                _ => format!("Day {day:02} example {example}"),
+            };
+            if violations.is_empty() {
+                println!("{name}: OK");
+            } else {
+                println!("{name}:");
+                for violation in violations {
+                    println!("  {violation}");
+                }
+            }
+        }
+    } else {
+        let json = serde_json::to_string_pretty(
+            &outputs
+                .iter()
+                .flat_map(|(day, example, violations)| {
+                    violations.iter().map(|violation| JsonViolation {
+                        day: *day,
+                        example: if *example == 0 { None } else { Some(*example) },
+                        part: violation.part.into(),
+                        assumption: violation.assumption,
+                        details: &violation.details,
+                    })
+                })
+                .collect::<Vec<_>>(),
+        )?;
+        println!("{json}");
+    }
+
+    ensure!(violated == 0, "{violated} assumption(s) violated");
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct JsonViolation<'a> {
+    day: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    example: Option<usize>,
+    part: u8,
+    assumption: &'a str,
+    details: &'a str,
+}
+
+/// Print the parsed inputs of the selected days as JSON. Both parts are dumped for days that parse
+/// the input per part, unless --part is given.
+fn print_parsed(cli: &Cli) -> Result<()> {
+    let mut outputs = Vec::new();
+    for (day, example, source) in inputs(cli)? {
+        let parts = match cli.part {
+            Some(part) => vec![Part::from(part)],
+            None if reuse_parsed(day)? => vec![Part1],
+            None => vec![Part1, Part2],
+        };
+        for part in parts {
+            outputs.push(JsonParsed {
+                day,
+                example: if example == 0 { None } else { Some(example) },
+                part: part.into(),
+                parsed: dump_parsed(day, source.input(day)?, part)?,
+            });
+        }
+    }
+
+    println!("{}", serde_json::to_string_pretty(&outputs)?);
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct JsonParsed {
+    day: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    example: Option<usize>,
+    part: u8,
+    parsed: serde_json::Value,
+}
+
+fn print_visualizations(cli: &Cli) -> Result<()> {
+    for (day, example, source) in inputs(cli)? {
+        let name = match example {
+            0 => format!("Day {day:02}"),
+            _ => format!("Day {day:02} example {example}"),
+        };
+        for part in [Part1, Part2] {
+            if cli.part.is_some_and(|only| only != u8::from(part)) {
+                continue;
+            }
+            match visualize_day(day, source.input(day)?, part)? {
+                Some(visualization) => {
+                    println!("{name} part {}:\n{visualization}\n", u8::from(part))
+                }
+                None => println!("{name} part {}: no visualization\n", u8::from(part)),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn render(cli: &Cli, path: &Path) -> Result<()> {
+    let inputs = inputs(cli)?;
+    let [(day, _, source)] = &inputs[..] else {
+        bail!("--render draws a single day, select one with --day (and --example)");
+    };
+
+    let paths = match cli.part {
+        Some(part) => vec![(Part::from(part), path.to_path_buf())],
+        None => [Part1, Part2]
+            .into_iter()
+            .map(|part| {
+                let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+                let extension = path.extension().unwrap_or_default().to_string_lossy();
+                let name = format!("{stem}-part{}.{extension}", u8::from(part));
+                (part, path.with_file_name(name))
+            })
+            .collect(),
+    };
+
+    for (part, path) in paths {
+        match render_day(*day, source.input(*day)?, part)? {
+            Some(image) => {
+                image.save(&path)?;
+                println!("Day {day:02} part {}: {}", u8::from(part), path.display());
+            }
+            None => println!("Day {day:02} part {}: no image", u8::from(part)),
+        }
+    }
+    Ok(())
+}
+
+fn print_fingerprints(cli: &Cli) -> Result<()> {
+    if let Some(path) = &cli.input {
+        let input = fs::read_to_string(path)?;
+        println!("{}", Fingerprint::of(&input));
+        return Ok(());
+    }
+
+    let example = cli
+        .example
+        .as_ref()
+        .and_then(|examples| examples.first().copied())
+        .unwrap_or(0);
+    for day in DAYS.iter().filter(|day| {
+        (cli.day.is_empty() || cli.day.contains(&day.day))
+            && (example == 0 || day.examples.iter().any(|e| e.example == example))
+    }) {
+        let input = get_input(day.day, example)?;
+        println!("Day {:02}: {}", day.day, Fingerprint::of(input));
+    }
+
+    Ok(())
+}
+
+fn fetch_inputs(
+    cli: &Cli,
+    fetcher: &Fetcher,
+    output_dir: Option<&Path>,
+    force: bool,
+) -> Result<()> {
+    let days = if cli.day.is_empty() {
+        vec![Local::now().day() as u8]
+    } else {
+        cli.day.clone()
+    };
+
+    for day in days {
+        let path = match output_dir {
+            Some(dir) => dir.join(format!("day{day:02}.txt")),
+            None => aoc2023::watch::input_path(day, 0),
+        };
+        let existing = fs::read_to_string(&path).unwrap_or_default();
+        if !existing.is_empty() && !force {
+            println!("Day {day:02}: {} exists, skipped", path.display());
+            continue;
+        }
+
+        let input = fetcher.input(day)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, input)?;
+        println!("Day {day:02}: saved {}", path.display());
+    }
+
+    if output_dir.is_none() {
+        println!("Rebuild to embed the new inputs");
+    }
+    Ok(())
+}
+
+/// Use the settings of the configuration files that aren't given on the command line.
+fn configure(cli: &mut Cli, config: Config) {
+    let format = config.format.map(|format| match format {
+        OutputFormat::Text => Format::Text,
+        OutputFormat::Json => Format::Json,
+    });
+    cli.format = cli
+        .format
+        .or(format)
+        .filter(|format| *format != Format::Text);
+    cli.input_dir = cli.input_dir.take().or(config.input_dir);
+    cli.session_file =
+        fetch::session_file(cli.session_file.as_deref(), config.session_file.as_deref());
+    cli.cache_file = cli.cache_file.take().or(config.cache);
+    cli.history_file = cli.history_file.take().or(config.history);
+    cli.jobs = cli.jobs.or(config.jobs);
+    cli.timeout = cli.timeout.or(config.timeout).filter(|_| !cli.no_timeout);
+}
+
+fn print_config(cli: &Cli, files: &[PathBuf]) -> Result<()> {
+    if files.is_empty() {
+        println!("# No {} found", config::FILE_NAME);
+    }
+    for file in files {
+        println!("# From {}", file.display());
+    }
+    let effective = Config {
+        input_dir: cli.input_dir.clone(),
+        format: Some(match cli.format {
+            Some(Format::Json) => OutputFormat::Json,
+            _ => OutputFormat::Text,
+        }),
+        session_file: cli.session_file.clone(),
+        cache: Some(match &cli.cache_file {
+            Some(path) => path.clone(),
+            None => Cache::default_path()?,
+        }),
+        history: Some(history_path(cli)?),
+        jobs: Some(cli.jobs.unwrap_or_else(rayon::current_num_threads)),
+        timeout: cli.timeout,
+    };
+    print!("{}", toml::to_string(&effective)?);
+    if effective.input_dir.is_none() {
+        println!("# input-dir is not set, the embedded inputs are used");
+    }
+    if env::var_os(fetch::SESSION_VAR).is_some() {
+        println!(
+            "# {} is set, so the session file isn't read",
+            fetch::SESSION_VAR
+        );
+    }
+    if effective.timeout.is_none() {
+        println!("# timeout is not set, parts run until they finish");
+    }
+    Ok(())
+}
+
+fn history_path(cli: &Cli) -> Result<PathBuf> {
+    match &cli.history_file {
+        Some(path) => Ok(path.clone()),
+        None => History::default_path(),
+    }
+}
+
+fn print_history(cli: &Cli) -> Result<()> {
+    let history = History::new(history_path(cli)?);
+    let (runs, invalid) = history.runs();
+    if !invalid.is_empty() {
+        let lines: Vec<_> = invalid.iter().map(ToString::to_string).collect();
+        eprintln!(
+            "{}",
+            paint(
+                format!(
+                    "Skipped {} invalid lines of {}: {}",
+                    invalid.len(),
+                    history.path().display(),
+                    lines.join(", ")
+                ),
+                Color::Yellow
+            )
+        );
+    }
+    let trends: Vec<_> = history::trends(&runs)
+        .into_iter()
+        .filter(|trend| cli.day.is_empty() || cli.day.contains(&trend.day))
+        .filter(|trend| cli.part.is_none_or(|part| part == trend.part))
+        .collect();
+
+    if cli.format.is_some() {
+        println!("{}", serde_json::to_string_pretty(&trends)?);
+        return Ok(());
+    }
+    if trends.is_empty() {
+        println!("No runs recorded");
+    }
+
+    let ms = |ms: Option<f64>| ms.map_or("-".to_string(), |ms| format!("{ms:.3} ms"));
+    for trend in trends {
+        let variant = trend
+            .variant
+            .as_ref()
+            .map(|variant| format!(" variant {variant}"))
+            .unwrap_or_default();
+        println!(
+            "Day {:02} part {} input {}{variant}: {} runs, first {}, best {}, last {} {}",
+            trend.day,
+            trend.part,
+            trend.input,
+            trend.runs,
+            ms(trend.first_ms),
+            ms(trend.best_ms),
+            ms(trend.last_ms),
+            paint(history::sparkline(&trend.timings), Color::Gray),
+        );
+        for change in &trend.changes {
+            let commit = change
+                .commit
+                .as_ref()
+                .map(|commit| format!(" (commit {})", &commit[..commit.len().min(10)]))
+                .unwrap_or_default();
+            let line = format!(
+                "  Answer changed from {} to {} at {}{commit}",
+                change.from, change.to, change.time
+            );
+            println!("{}", paint(line, Color::Yellow));
+        }
+        if !matches!(trend.last, Outcome::Answer { .. }) {
+            println!(
+                "  {}",
+                paint(format!("Last run: {}", trend.last), Color::Red)
+            );
+        }
+    }
+    Ok(())
+}
+
+fn encrypt_inputs(cli: &Cli) -> Result<()> {
+    for day in DAYS.iter().map(|day| day.day) {
+        let path = aoc2023::watch::input_path(day, 0);
+        let selected = cli.day.contains(&day);
+        if selected || (cli.day.is_empty() && fs::metadata(&path).is_ok_and(|file| file.len() > 0))
+        {
+            let encrypted = encrypt::encrypt_input(day)?;
+            println!("Day {day:02}: saved {}", encrypted.display());
+        }
+    }
+    Ok(())
+}
+
+fn submit(runner: &Runner, fetcher: &Fetcher) -> Result<()> {
+    let cli = runner.cli;
+    let [day] = cli.day[..] else {
+        bail!("Submit needs a single --day");
+    };
+    let part = match cli.part.context("Submit needs --part")? {
+        1 => Part1,
+        _ => Part2,
+    };
+    let source = match &cli.input {
+        Some(path) => Source::file(path)?,
+        None => Source::of(cli, day)?,
+    };
+
+    let answer = match runner.run_part(day, part, &source)? {
+        Outcome::Answer { output } => output,
+        outcome => bail!("Day {day} part {} has no answer: {outcome}", u8::from(part)),
+    };
+    let mut log = GuessLog::open(GuessLog::default_path(&fetcher.base_url)?)?;
+    log.check(day, u8::from(part), &answer)?;
+
+    println!("Day {day:02} part {}: submitting {answer}", u8::from(part));
+    let page = fetcher.answer(day, u8::from(part), &answer)?;
+    // The answer may have been judged, so log it before failing on a response that isn't understood
+    let parsed = Verdict::parse(&page);
+    let verdict = match &parsed {
+        Ok(verdict) => verdict.clone(),
+        Err(_) => Verdict::unrecognized(&page),
+    };
+    let color = match verdict {
+        Verdict::Correct => Color::Green,
+        Verdict::RateLimited { .. } | Verdict::WrongLevel | Verdict::Unrecognized { .. } => {
+            Color::Yellow
+        }
+        _ => Color::Red,
+    };
+    println!("{}", paint(&verdict, color));
+
+    log.record(Guess {
+        day,
+        part: u8::from(part),
+        answer,
+        verdict,
+        time: Local::now().to_rfc3339(),
+    })?;
+    parsed.map(|_| ())
+}
+
+/// Runs days as requested on the command line, answering from the cache when possible.
+struct Runner<'a> {
+    cli: &'a Cli,
+    cache: Option<RefCell<Cache>>,
+    history: Option<History>,
+}
+
+impl<'a> Runner<'a> {
+    fn new(cli: &'a Cli) -> Result<Self> {
+        let cache = if cli.no_cache || cli.variant.is_some() {
+            None
+        } else {
+            let path = match &cli.cache_file {
+                Some(path) => path.clone(),
+                None => Cache::default_path()?,
+            };
+            Some(RefCell::new(Cache::open(path)?))
+        };
+        let history = match cli.no_history {
+            true => None,
+            false => Some(History::new(history_path(cli)?)),
+        };
+        Ok(Self {
+            cli,
+            cache,
+            history,
+        })
+    }
+
+    /// A runner without cache and history, for runs on inputs that aren't worth remembering.
+    fn uncached(cli: &'a Cli) -> Self {
+        Self {
+            cli,
+            cache: None,
+            history: None,
+        }
+    }
+
+    fn run(&self, day: u8, source: &Source) -> Result<(Option<Outcome>, Option<Outcome>)> {
+        let part1 = self.cli.part.is_none() || self.cli.part.unwrap() == 1;
+        let part2 = self.cli.part.is_none() || self.cli.part.unwrap() == 2;
+
+        if self.cli.isolate || self.cli.timeout.is_some() || self.cli.variant.is_some() {
+            let run_part =
+                |run: bool, part: Part| run.then(|| self.run_part(day, part, source)).transpose();
+            Ok((run_part(part1, Part1)?, run_part(part2, Part2)?))
+        } else {
+            let input = source.input(day)?;
+            let cached =
+                |run: bool, part: Part| run.then(|| self.cached(day, part, input)).flatten();
+            let (cached1, cached2) = (cached(part1, Part1), cached(part2, Part2));
+
+            let (run1, run2) = (part1 && cached1.is_none(), part2 && cached2.is_none());
+            let (output1, output2) = run_day_timed(day, input, run1, run2)?;
+
+            // Only the part that failed is recorded with the error, the answer of part 1 is kept
+            // when part 2 fails
+            let answer =
+                |part: Part, cached: Option<String>, output: Option<(Result<String>, Duration)>| {
+                    let Some((output, elapsed)) = output else {
+                        return Ok(cached.map(|output| Outcome::Answer { output }));
+                    };
+                    let outcome = match &output {
+                        Ok(output) => {
+                            self.store(day, part, input, output);
+                            Outcome::Answer {
+                                output: output.clone(),
+                            }
+                        }
+                        Err(error) => Outcome::Error {
+                            message: format!("{error:#}"),
+                        },
+                    };
+                    self.record(day, part, input, &outcome, elapsed)?;
+                    output.map(|output| Some(Outcome::Answer { output }))
+                };
+            let part1 = answer(Part1, cached1, output1)?;
+            Ok((part1, answer(Part2, cached2, output2)?))
+        }
+    }
+
+    /// Like [`Runner::run`], but with JSON output an error is reported as the outcome of the parts,
+    /// instead of stopping the whole run.
+    fn run_reported(&self, day: u8, source: &Source) -> Result<(Option<Outcome>, Option<Outcome>)> {
+        match self.run(day, source) {
+            Err(error) if self.cli.format.is_some() => {
+                let outcome = Outcome::Error {
+                    message: format!("{error:#}"),
+                };
+                let part1 = self.cli.part.is_none() || self.cli.part.unwrap() == 1;
+                let part2 = self.cli.part.is_none() || self.cli.part.unwrap() == 2;
+                Ok((part1.then(|| outcome.clone()), part2.then_some(outcome)))
+            }
+            result => result,
+        }
+    }
+
+    /// Run a single part, isolated in a child process or with a timeout if requested.
+    fn run_part(&self, day: u8, part: Part, source: &Source) -> Result<Outcome> {
+        let input = source.input(day)?;
+        if let Some(output) = self.cached(day, part, input) {
+            return Ok(Outcome::Answer { output });
+        }
+
+        let variant = self.variant();
+        let start = Instant::now();
+        let outcome = if self.cli.isolate {
+            run_isolated(self.cli, day, part, source)
+        } else if let Some(timeout) = self.cli.timeout {
+            let timeout = Duration::from_secs_f64(timeout);
+            cancel::run_day_variant_with_timeout(day, part, variant, input, timeout)
+        } else {
+            run_day_variant(day, part, variant, input).map(|output| Outcome::Answer { output })
+        };
+        let elapsed = start.elapsed();
+
+        let outcome = match outcome {
+            Ok(outcome) => outcome,
+            Err(error) => {
+                let message = format!("{error:#}");
+                self.record(day, part, input, &Outcome::Error { message }, elapsed)?;
+                return Err(error);
+            }
+        };
+        if let Outcome::Answer { output } = &outcome {
+            self.store(day, part, input, output);
+        }
+        self.record(day, part, input, &outcome, elapsed)?;
+
+        Ok(outcome)
+    }
+
+    fn variant(&self) -> &'static str {
+        match &self.cli.variant {
+            Some(variant) => variant.clone().leak(),
+            None => variant::DEFAULT,
+        }
+    }
+
+    fn cached(&self, day: u8, part: Part, input: &str) -> Option<String> {
+        self.cache
+            .as_ref()
+            .and_then(|cache| cache.borrow().get(day, part, input).map(str::to_string))
+    }
+
+    fn store(&self, day: u8, part: Part, input: &str, output: &str) {
+        if let Some(cache) = &self.cache {
+            cache
+                .borrow_mut()
+                .insert(day, part, input, output.to_string());
+        }
+    }
+
+    /// Add a computed outcome to the history.
+    fn record(
+        &self,
+        day: u8,
+        part: Part,
+        input: &str,
+        outcome: &Outcome,
+        elapsed: Duration,
+    ) -> Result<()> {
+        let Some(history) = &self.history else {
+            return Ok(());
+        };
+        history.record(&Run {
+            time: Local::now().to_rfc3339(),
+            commit: history::commit().map(str::to_string),
+            day,
+            part: u8::from(part),
+            input: Fingerprint::of(input),
+            variant: self.cli.variant.clone(),
+            outcome: outcome.clone(),
+            elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+        })
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(cache) = &self.cache {
+            cache.borrow_mut().save()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn run_isolated(cli: &Cli, day: u8, part: Part, source: &Source) -> Result<Outcome> {
+    use std::io::Write;
+
+    use aoc2023::isolate::{self, Limits};
+
+    let mut command = std::process::Command::new(std::env::current_exe()?);
+    // Kept until the child is done, then removed
+    let mut generated = None;
+    command
+        .arg("--isolated")
+        .args(["--day", &day.to_string()])
+        .args(["--part", &u8::from(part).to_string()]);
+    match source {
+        Source::Embedded(0) => {}
+        Source::Embedded(example) => {
+            command.args(["--example", &example.to_string()]);
+        }
+        Source::File { path, .. } => {
+            command.arg("--input").arg(path);
+        }
+        Source::Generated { input } => {
+            let mut file = tempfile::Builder::new()
+                .prefix(&format!("aoc2023-day{day:02}-generated-"))
+                .suffix(".txt")
+                .tempfile()?;
+            file.write_all(input.as_bytes())?;
+            command.arg("--input").arg(file.path());
+            generated = Some(file);
+        }
+    }
+    match cli.timeout {
+        Some(timeout) => command.args(["--timeout", &timeout.to_string()]),
+        // Or the child would use the timeout of the configuration files
+        None => command.arg("--no-timeout"),
+    };
+    if let Some(variant) = &cli.variant {
+        command.args(["--variant", variant]);
+    }
+    if let Some(config) = &cli.config {
+        command.arg("--config").arg(config);
+    }
+
+    let outcome = isolate::run_isolated(
+        command,
+        Limits {
+            memory_mib: cli.memory_limit,
+            cpu_seconds: cli.cpu_limit,
+        },
+    );
+    drop(generated);
+    outcome
+}
+
+#[cfg(not(target_os = "linux"))]
+fn run_isolated(_cli: &Cli, _day: u8, _part: Part, _source: &Source) -> Result<Outcome> {
+    bail!("--isolate is only supported on Linux")
+}
+
+fn run_isolated_child(cli: &Cli) -> Result<()> {
+    let day = *cli.day.first().context("--isolated requires --day")?;
+    let part = Part::from(cli.part.context("--isolated requires --part")?);
+    let source = match (&cli.input, &cli.example) {
+        (Some(path), _) => Source::file(path)?,
+        (None, Some(examples)) => Source::Embedded(examples.first().copied().unwrap_or(0)),
+        (None, None) => Source::of(cli, day)?,
+    };
+
+    let outcome = Runner::uncached(cli)
+        .run_part(day, part, &source)
+        .unwrap_or_else(|error| Outcome::Error {
+            message: format!("{error:#}"),
+        });
+
+    println!("{}", serde_json::to_string(&outcome)?);
+
+    Ok(())
+}
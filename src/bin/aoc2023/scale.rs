@@ -0,0 +1,142 @@
+use std::time::{Duration, Instant};
+
+use aoc2023::{prelude::*, scale::fit_exponent};
+use serde::Serialize;
+
+use super::{Runner, Source};
+
+#[derive(Serialize)]
+struct Report {
+    day: u8,
+    part: u8,
+    exponent: Option<f64>,
+    samples: Vec<Sample>,
+}
+
+#[derive(Serialize)]
+struct Sample {
+    size: usize,
+    #[serde(flatten)]
+    outcome: Outcome,
+    elapsed_ms: f64,
+}
+
+pub struct Sizes {
+    pub min: usize,
+    pub max: usize,
+    pub budget: f64,
+    pub repeat: usize,
+}
+
+/// Time the parts of the selected days on generated inputs of doubling sizes, and fit the
+/// exponent of their complexity. A part stops growing when a run takes longer than the budget or
+/// doesn't give an answer. Without --timeout, parts time out after twice the budget.
+pub fn run(runner: &Runner, sizes: &Sizes) -> Result<()> {
+    let cli = runner.cli;
+    let mut reports = Vec::new();
+
+    for day in DAYS
+        .iter()
+        .filter(|day| cli.day.is_empty() || cli.day.contains(&day.day))
+    {
+        for part in [Part1, Part2] {
+            if cli.part.is_some_and(|only| only != u8::from(part)) {
+                continue;
+            }
+
+            let mut samples = Vec::new();
+            let mut size = sizes.min.max(1);
+            while size <= sizes.max {
+                let Some(input) = generate_input(day.day, &mut Rng::with_seed(size as u64), size)?
+                else {
+                    break;
+                };
+                let source = Source::Generated {
+                    input: input.leak(),
+                };
+
+                let mut sample = time(runner, day.day, part, &source, sizes.budget);
+                for _ in 1..sizes.repeat {
+                    if !matches!(sample.outcome, Outcome::Answer { .. }) {
+                        break;
+                    }
+                    let repeated = time(runner, day.day, part, &source, sizes.budget);
+                    sample.elapsed_ms = sample.elapsed_ms.min(repeated.elapsed_ms);
+                }
+
+                let done = sample.elapsed_ms > sizes.budget * 1000.0
+                    || !matches!(sample.outcome, Outcome::Answer { .. });
+                samples.push(Sample { size, ..sample });
+                if done {
+                    break;
+                }
+                size *= 2;
+            }
+
+            if samples.is_empty() {
+                continue;
+            }
+
+            let exponent = fit_exponent(
+                &samples
+                    .iter()
+                    .filter(|sample| matches!(sample.outcome, Outcome::Answer { .. }))
+                    .map(|sample| (sample.size as f64, sample.elapsed_ms))
+                    .collect::<Vec<_>>(),
+            );
+            reports.push(Report {
+                day: day.day,
+                part: part.into(),
+                exponent,
+                samples,
+            });
+        }
+    }
+
+    if cli.format.is_some() {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    } else {
+        print_reports(&reports);
+    }
+
+    Ok(())
+}
+
+fn time(runner: &Runner, day: u8, part: Part, source: &Source, budget: f64) -> Sample {
+    let start = Instant::now();
+    let outcome = if runner.cli.isolate || runner.cli.timeout.is_some() {
+        runner.run_part(day, part, source)
+    } else {
+        source.input(day).and_then(|input| {
+            let timeout = Duration::from_secs_f64(2.0 * budget);
+            cancel::run_day_part_with_timeout(day, part, input, timeout)
+        })
+    }
+    .unwrap_or_else(|error| Outcome::Error {
+        message: format!("{error:#}"),
+    });
+    Sample {
+        size: 0,
+        outcome,
+        elapsed_ms: start.elapsed().as_secs_f64() * 1000.0,
+    }
+}
+
+fn print_reports(reports: &[Report]) {
+    for report in reports {
+        let complexity = match report.exponent {
+            Some(exponent) => format!("O(n^{exponent:.2})"),
+            None => "unknown".to_string(),
+        };
+        println!("Day {:02} part {}: {complexity}", report.day, report.part);
+
+        for sample in &report.samples {
+            match &sample.outcome {
+                Outcome::Answer { .. } => {
+                    println!("  n = {:6}: {:10.3} ms", sample.size, sample.elapsed_ms)
+                }
+                outcome => println!("  n = {:6}: {outcome}", sample.size),
+            }
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use std::{collections::BTreeMap, env, fs, path::Path, process, thread, time::Duration};
+
+use aoc2023::{
+    prelude::*,
+    watch::{day_dir, input_path, Snapshot},
+};
+use chrono::Local;
+
+use super::{Runner, Source};
+
+/// File with the previous answers, kept when watch restarts itself after a rebuild.
+const STATE: &str = "AOC2023_WATCH_STATE";
+
+/// Answers by day, example (0 for the input) and part.
+type Answers = BTreeMap<(u8, usize, u8), String>;
+
+/// Run the selected days and examples, and again whenever a file of those days changes. Inputs
+/// are read from the source tree. When a source file changes, the binary is rebuilt and restarted.
+pub fn run(runner: &Runner, days: &[u8], interval: Duration) -> Result<()> {
+    let dirs: Vec<_> = days.iter().map(|day| day_dir(*day)).collect();
+    let mut snapshot = Snapshot::take(&dirs)?;
+    let mut previous = load_state();
+    // Once rebuilt, the running executable is replaced and its path no longer resolves
+    let executable = env::current_exe()?;
+
+    loop {
+        println!("{}", paint(Local::now().format("%H:%M:%S"), Color::Gray));
+        previous = run_days(runner, days, &previous)?;
+        println!("Watching for changes");
+
+        let changes = loop {
+            thread::sleep(interval);
+            let newer = Snapshot::take(&dirs)?;
+            let changes = snapshot.changes(&newer);
+            snapshot = newer;
+            if !changes.is_empty() {
+                break changes;
+            }
+        };
+
+        if changes
+            .iter()
+            .any(|path| path.extension().is_some_and(|extension| extension == "rs"))
+        {
+            rebuild(&executable, &previous)?;
+        }
+    }
+}
+
+fn run_days(runner: &Runner, days: &[u8], previous: &Answers) -> Result<Answers> {
+    let cli = runner.cli;
+    let mut answers = Answers::new();
+
+    for day in DAYS.iter().filter(|day| days.contains(&day.day)) {
+        let mut inputs = vec![(0, None)];
+        if let Some(examples) = &cli.example {
+            inputs = day
+                .examples
+                .iter()
+                .filter(|example| examples.is_empty() || examples.contains(&example.example))
+                .map(|example| (example.example, Some(example)))
+                .collect();
+        }
+
+        for (example, definition) in inputs {
+            let name = match example {
+                0 => format!("Day {:02}", day.day),
+                _ => format!("Day {:02} example {example}", day.day),
+            };
+            let source = match Source::file(&input_path(day.day, example)) {
+                Ok(source) => source,
+                Err(error) => {
+                    println!("{name}: {}", paint(format!("{error:#}"), Color::Red));
+                    continue;
+                }
+            };
+
+            for part in [Part1, Part2] {
+                if cli.part.is_some_and(|only| only != u8::from(part)) {
+                    continue;
+                }
+                let expected = definition.and_then(|example| {
+                    example.parts.iter().find(|expected| expected.part == part)
+                });
+                if definition.is_some() && expected.is_none() {
+                    continue;
+                }
+
+                let outcome = runner
+                    .run_part(day.day, part, &source)
+                    .unwrap_or_else(|error| Outcome::Error {
+                        message: format!("{error:#}"),
+                    });
+                let answer = outcome.to_string();
+                let key = (day.day, example, u8::from(part));
+
+                let mut line = format!("{name} part {}: {answer}", u8::from(part));
+                if let Some(expected) = expected {
+                    let agrees = match &outcome {
+                        Outcome::Error { message } if expected.error => {
+                            message.contains(expected.expected)
+                        }
+                        Outcome::Answer { output } if !expected.error => {
+                            output == expected.expected
+                        }
+                        _ => false,
+                    };
+                    line += &if agrees {
+                        format!(" {}", paint("OK", Color::Green))
+                    } else if expected.error {
+                        let expected = format!("expected error \"{}\"", expected.expected);
+                        format!(" {}", paint(expected, Color::Red))
+                    } else {
+                        let expected = format!("expected {}", expected.expected);
+                        format!(" {}", paint(expected, Color::Red))
+                    };
+                }
+                match previous.get(&key) {
+                    Some(before) if *before != answer => {
+                        line += &format!(" {}", paint(format!("(was {before})"), Color::Yellow));
+                    }
+                    _ => {}
+                }
+                println!("{line}");
+
+                answers.insert(key, answer);
+            }
+        }
+    }
+
+    Ok(answers)
+}
+
+/// Rebuild the binary and restart it with the same arguments, keeping the answers to compare
+/// with. If the build fails, the old binary keeps watching.
+fn rebuild(executable: &Path, answers: &Answers) -> Result<()> {
+    println!("Sources changed, rebuilding");
+    let mut build = process::Command::new(env::var("CARGO").unwrap_or("cargo".to_string()));
+    build
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["build", "--bin", "aoc2023"]);
+    if !cfg!(debug_assertions) {
+        build.arg("--release");
+    }
+    if !build.status()?.success() {
+        println!("{}", paint("Build failed", Color::Red));
+        return Ok(());
+    }
+
+    let state = env::temp_dir().join(format!("aoc2023-watch-{}.json", process::id()));
+    fs::write(
+        &state,
+        serde_json::to_string(&answers.iter().collect::<Vec<_>>())?,
+    )?;
+    restart(executable, &state)
+}
+
+#[cfg(unix)]
+fn restart(executable: &Path, state: &Path) -> Result<()> {
+    use std::os::unix::process::CommandExt;
+
+    let error = process::Command::new(executable)
+        .args(env::args_os().skip(1))
+        .env(STATE, state)
+        .exec();
+    Err(error).context("Cannot restart after rebuilding")
+}
+
+#[cfg(not(unix))]
+fn restart(_executable: &Path, _state: &Path) -> Result<()> {
+    bail!("Rebuilt, restart watch to run the new build")
+}
+
+fn load_state() -> Answers {
+    let Some(path) = env::var_os(STATE) else {
+        return Answers::new();
+    };
+    let answers = fs::read_to_string(&path)
+        .ok()
+        .and_then(|state| serde_json::from_str::<Vec<((u8, usize, u8), String)>>(&state).ok())
+        .unwrap_or_default();
+    let _ = fs::remove_file(path);
+    answers.into_iter().collect()
+}
@@ -0,0 +1,121 @@
+use std::{collections::HashMap, fs, path::PathBuf, time::UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{fingerprint::Fingerprint, Part, Result};
+
+/// On disk cache of answers, keyed by day, part and input. All entries are dropped when the
+/// build fingerprint changes, so a rebuild always invalidates the cache.
+pub struct Cache {
+    path: PathBuf,
+    file: CacheFile,
+    dirty: bool,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct CacheFile {
+    build: String,
+    answers: HashMap<String, String>,
+}
+
+impl Cache {
+    /// Open the cache file at `path`. A missing, unreadable or outdated cache file results in an
+    /// empty cache.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let build = build_fingerprint()?;
+
+        let file = fs::read_to_string(&path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<CacheFile>(&json).ok())
+            .filter(|file| file.build == build)
+            .unwrap_or(CacheFile {
+                build,
+                answers: HashMap::new(),
+            });
+
+        Ok(Self {
+            path,
+            file,
+            dirty: false,
+        })
+    }
+
+    /// Default location of the cache file: next to the executable, so every build profile has its
+    /// own cache.
+    pub fn default_path() -> Result<PathBuf> {
+        Ok(std::env::current_exe()?.with_file_name("aoc2023-cache.json"))
+    }
+
+    pub fn get(&self, day: u8, part: Part, input: &str) -> Option<&str> {
+        self.file
+            .answers
+            .get(&Self::key(day, part, input))
+            .map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u8, part: Part, input: &str, output: String) {
+        self.file
+            .answers
+            .insert(Self::key(day, part, input), output);
+        self.dirty = true;
+    }
+
+    /// Write the cache to disk, if anything changed.
+    pub fn save(&mut self) -> Result<()> {
+        if self.dirty {
+            fs::write(&self.path, serde_json::to_string(&self.file)?)?;
+            self.dirty = false;
+        }
+        Ok(())
+    }
+
+    fn key(day: u8, part: Part, input: &str) -> String {
+        format!("{day:02}-{}-{}", u8::from(part), Fingerprint::of(input))
+    }
+}
+
+/// Fingerprint of the running executable. It changes on every rebuild.
+pub fn build_fingerprint() -> Result<String> {
+    let metadata = fs::metadata(std::env::current_exe()?)?;
+    let modified = metadata.modified()?.duration_since(UNIX_EPOCH)?;
+    Ok(format!(
+        "{}-{}-{}",
+        env!("CARGO_PKG_VERSION"),
+        metadata.len(),
+        modified.as_nanos()
+    ))
+}
+
+#[cfg(test)]
+#[test]
+fn answers_are_kept_until_the_build_changes() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cache.json");
+
+    let mut cache = Cache::open(&path).unwrap();
+    assert_eq!(cache.get(1, Part::Part1, "input"), None);
+    cache.insert(1, Part::Part1, "input", "42".to_string());
+    assert_eq!(cache.get(1, Part::Part1, "input"), Some("42"));
+    assert_eq!(cache.get(1, Part::Part2, "input"), None);
+    assert_eq!(cache.get(1, Part::Part1, "other input"), None);
+    cache.save().unwrap();
+
+    let cache = Cache::open(&path).unwrap();
+    assert_eq!(cache.get(1, Part::Part1, "input"), Some("42"));
+
+    // A cache written by another build is dropped
+    let mut file: CacheFile = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    file.build = "other build".to_string();
+    fs::write(&path, serde_json::to_string(&file).unwrap()).unwrap();
+    assert_eq!(
+        Cache::open(&path).unwrap().get(1, Part::Part1, "input"),
+        None
+    );
+
+    fs::write(&path, "not json").unwrap();
+    assert_eq!(
+        Cache::open(&path).unwrap().get(1, Part::Part1, "input"),
+        None
+    );
+}
@@ -0,0 +1,115 @@
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::{error::Error, run_day_variant, variant, Outcome, Part, Result};
+
+/// Worker threads get a large stack, because some solutions recurse deeply on the real input.
+const WORKER_STACK_SIZE: usize = 32 * 1024 * 1024;
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancellationToken>> = const { RefCell::new(None) };
+}
+
+/// Shared flag to ask a running solution to stop.
+#[derive(Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Make this token the one polled by [`check`] and [`is_cancelled`] on the current thread.
+    pub fn install(&self) {
+        CURRENT.with(|current| *current.borrow_mut() = Some(self.clone()));
+    }
+}
+
+/// Returns `true` if the token installed on the current thread is cancelled.
+/// Without an installed token a solution is never cancelled.
+pub fn is_cancelled() -> bool {
+    CURRENT.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    })
+}
+
+/// Long running solutions can call this to stop early with [`Error::Cancelled`].
+pub fn check() -> Result<()> {
+    if is_cancelled() {
+        Err(Error::Cancelled)?
+    }
+    Ok(())
+}
+
+/// Run a single part on a worker thread. If it takes longer than `timeout`, the part is cancelled
+/// and [`Outcome::Timeout`] is returned. Solutions that don't poll the token keep running in the
+/// background until the process exits.
+pub fn run_day_part_with_timeout(
+    day: u8,
+    part: Part,
+    input: &'static str,
+    timeout: Duration,
+) -> Result<Outcome> {
+    run_day_variant_with_timeout(day, part, variant::DEFAULT, input, timeout)
+}
+
+/// Like [`run_day_part_with_timeout`], with the named solver variant.
+pub fn run_day_variant_with_timeout(
+    day: u8,
+    part: Part,
+    variant: &'static str,
+    input: &'static str,
+    timeout: Duration,
+) -> Result<Outcome> {
+    let token = CancellationToken::default();
+    let (sender, receiver) = mpsc::channel();
+
+    let worker_token = token.clone();
+    thread::Builder::new()
+        .name(format!("day{day:02}"))
+        .stack_size(WORKER_STACK_SIZE)
+        .spawn(move || {
+            worker_token.install();
+            let _ = sender.send(run_day_variant(day, part, variant, input));
+        })?;
+
+    match receiver.recv_timeout(timeout) {
+        Ok(output) => Ok(Outcome::Answer { output: output? }),
+        Err(RecvTimeoutError::Timeout) => {
+            token.cancel();
+            Ok(Outcome::Timeout {
+                timeout_ms: timeout.as_millis() as u64,
+            })
+        }
+        Err(RecvTimeoutError::Disconnected) => Err(Error::WorkerPanicked(day))?,
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn cancelled_token_fails_check() {
+    let token = CancellationToken::default();
+    token.install();
+    assert!(check().is_ok());
+
+    token.cancel();
+    assert!(matches!(
+        check().unwrap_err().downcast_ref::<Error>(),
+        Some(Error::Cancelled)
+    ));
+}
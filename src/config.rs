@@ -0,0 +1,182 @@
+//! Defaults for the command line from `aoc.toml` files. The user file (`~/.config/aoc/aoc.toml`)
+//! is read first, then the project file (the first `aoc.toml` in the current directory or one of
+//! its parents), and settings of later files win. Command line flags override both.
+//!
+//! ```toml
+//! input-dir = "inputs"       # read the input of day 1 from inputs/day01.txt, if it exists
+//! format = "json"            # or "text"
+//! session-file = "~/.aoc"    # file with the session token of fetch and submit
+//! cache = "answers.json"     # file with the cached answers
+//! history = "history.jsonl"  # file with the history of runs
+//! jobs = 4                   # number of threads of parallel solutions
+//! timeout = 10.0             # maximum number of seconds per part
+//! ```
+//!
+//! Relative paths are relative to the directory of the file they are in.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+
+pub const FILE_NAME: &str = "aoc.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+    /// Directory with inputs named `dayXX.txt`, used instead of the embedded inputs
+    pub input_dir: Option<PathBuf>,
+    pub format: Option<OutputFormat>,
+    /// File with the session token
+    pub session_file: Option<PathBuf>,
+    /// File with the cached answers
+    pub cache: Option<PathBuf>,
+    /// File with the history of runs
+    pub history: Option<PathBuf>,
+    /// Number of threads of parallel solutions
+    pub jobs: Option<usize>,
+    /// Maximum number of seconds per part, `--no-timeout` overrides it
+    pub timeout: Option<f64>,
+}
+
+impl Config {
+    /// Parse a configuration file in directory `dir`, which relative paths are resolved against.
+    pub fn parse(toml: &str, dir: &Path) -> Result<Config> {
+        let mut config: Config = toml::from_str(toml)?;
+        for path in [
+            &mut config.input_dir,
+            &mut config.session_file,
+            &mut config.cache,
+            &mut config.history,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            *path = resolve(path, dir);
+        }
+        if let Some(timeout) = config.timeout {
+            Duration::try_from_secs_f64(timeout).map_err(|_| {
+                anyhow!("timeout must be a finite, non-negative number of seconds, not {timeout}")
+            })?;
+        }
+        Ok(config)
+    }
+
+    /// Read a configuration file.
+    pub fn read(path: &Path) -> Result<Config> {
+        let toml = fs::read_to_string(path)
+            .with_context(|| format!("Cannot read configuration file {}", path.display()))?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+        Self::parse(&toml, dir)
+            .with_context(|| format!("Invalid configuration file {}", path.display()))
+    }
+
+    /// The merged user and project files, or only `file` if given, with the files that were read.
+    pub fn load(file: Option<&Path>) -> Result<(Config, Vec<PathBuf>)> {
+        let files: Vec<_> = match file {
+            Some(file) => vec![file.to_path_buf()],
+            None => [user_file(), project_file()]
+                .into_iter()
+                .flatten()
+                .filter(|path| path.is_file())
+                .collect(),
+        };
+
+        let mut config = Config::default();
+        for path in &files {
+            config = config.or(Self::read(path)?);
+        }
+        Ok((config, files))
+    }
+
+    /// The settings of `self`, and of `other` where `other` sets them.
+    pub fn or(self, other: Config) -> Config {
+        Config {
+            input_dir: other.input_dir.or(self.input_dir),
+            format: other.format.or(self.format),
+            session_file: other.session_file.or(self.session_file),
+            cache: other.cache.or(self.cache),
+            history: other.history.or(self.history),
+            jobs: other.jobs.or(self.jobs),
+            timeout: other.timeout.or(self.timeout),
+        }
+    }
+}
+
+/// `$XDG_CONFIG_HOME/aoc/aoc.toml`, by default `~/.config/aoc/aoc.toml`.
+pub fn user_file() -> Option<PathBuf> {
+    let dir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(&env::var_os("HOME")?).join(".config"),
+    };
+    Some(dir.join("aoc").join(FILE_NAME))
+}
+
+/// `$XDG_DATA_HOME/aoc`, by default `~/.local/share/aoc`: the directory of data that has to survive
+/// `cargo clean`, like the log of submitted answers.
+pub fn data_dir() -> Option<PathBuf> {
+    let dir = match env::var_os("XDG_DATA_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(&env::var_os("HOME")?)
+            .join(".local")
+            .join("share"),
+    };
+    Some(dir.join("aoc"))
+}
+
+/// The first `aoc.toml` in the current directory or its parents.
+pub fn project_file() -> Option<PathBuf> {
+    env::current_dir()
+        .ok()?
+        .ancestors()
+        .map(|dir| dir.join(FILE_NAME))
+        .find(|path| path.is_file())
+}
+
+/// Resolve `~/` and relative paths.
+fn resolve(path: &Path, dir: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => dir.join(path),
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn files_are_parsed_and_merged() {
+    let dir = Path::new("/project");
+    let user = Config::parse("format = \"json\"\ntimeout = 2.5\njobs = 8\n", dir).unwrap();
+    let project = Config::parse(
+        "input-dir = \"inputs\"\ncache = \"/tmp/answers.json\"\njobs = 2\n",
+        dir,
+    )
+    .unwrap();
+
+    assert_eq!(
+        user.or(project),
+        Config {
+            input_dir: Some(PathBuf::from("/project/inputs")),
+            format: Some(OutputFormat::Json),
+            session_file: None,
+            cache: Some(PathBuf::from("/tmp/answers.json")),
+            history: None,
+            jobs: Some(2),
+            timeout: Some(2.5),
+        }
+    );
+    assert!(Config::parse("timeuot = 1.0", dir).is_err());
+    assert!(Config::parse("format = \"yaml\"", dir).is_err());
+    assert!(Config::parse("timeout = -1.0", dir).is_err());
+    assert!(Config::parse("timeout = nan", dir).is_err());
+}
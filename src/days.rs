@@ -148,6 +148,10 @@ crate::days! {
         example 2 {
             part 2 expected "71",
         }
+        example 3 {
+            part 1 expected error "Parse error at line 2, column 3",
+            part 2 expected error "Parse error at line 2, column 3",
+        }
     }
 
     Day 18 {
@@ -165,6 +169,10 @@ crate::days! {
             part 1 expected "19114",
             part 2 expected "167409079868000",
         }
+        example 2 {
+            part 1 expected error "Workflow xyz not found",
+            part 2 expected error "Workflow xyz not found",
+        }
     }
 
     Day 20 {
@@ -176,7 +184,11 @@ crate::days! {
         }
     }
 
-    Day 21 { }
+    Day 21 {
+        example 1 {
+            part 1 expected "42",
+        }
+    }
 
     Day 22 {
         example 1 {
@@ -17,6 +17,10 @@ impl Day for Day01 {
         false
     }
 
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(Generator::input(rng, size))
+    }
+
     fn parse(input: &'static str, _part: Part) -> Result<Self::Parsed> {
         Ok(input)
     }
@@ -59,9 +63,8 @@ impl Parser {
                     (first, last)
                 }
             },
-        )(s).map(|(s, (first, last))| {
-            (s, first.map(|first| 10 * first + last))
-        })
+        )(s)
+        .map(|(s, (first, last))| (s, first.map(|first| 10 * first + last)))
     }
 
     fn spelled(s: &str) -> IResult<&str, Option<u32>> {
@@ -84,3 +87,28 @@ impl Parser {
         anychar(s).map(|(s, c)| (s, c.to_digit(10)))
     }
 }
+
+struct Generator;
+impl Generator {
+    fn input(rng: &mut Rng, size: usize) -> String {
+        const WORDS: [&str; 9] = [
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ];
+
+        (0..size)
+            .map(|_| {
+                // Every line needs a digit for part 1
+                let mut line = rng.digit(10).to_string();
+                for _ in 0..rng.usize(0..8) {
+                    match rng.u8(0..3) {
+                        0 => line.push(rng.digit(10)),
+                        1 => line.push_str(WORDS[rng.usize(..WORDS.len())]),
+                        _ => line.push(rng.lowercase()),
+                    }
+                }
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
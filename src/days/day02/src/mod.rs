@@ -2,11 +2,12 @@ use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{newline, u32},
-    combinator::{all_consuming, map, opt},
+    combinator::{all_consuming, cut, map, opt},
+    error::context,
     multi::{fold_many1, separated_list1},
     sequence::{pair, preceded, separated_pair},
-    IResult,
 };
+use serde::Serialize;
 
 use super::inputs::{Inputs, INPUTS};
 use crate::prelude::*;
@@ -22,6 +23,10 @@ impl Day for Day02 {
         true
     }
 
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(Generator::input(rng, size))
+    }
+
     fn parse(input: &'static str, _part: Part) -> Result<Self::Parsed> {
         Games::try_from(input)
     }
@@ -45,16 +50,16 @@ impl Day for Day02 {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Games(Vec<Game>);
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Game {
     number: u32,
     grabs: Vec<Grab>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize)]
 pub struct Grab {
     red: u32,
     green: u32,
@@ -74,8 +79,17 @@ impl Games {
         self.0.iter()
     }
 
-    fn parse(s: &str) -> IResult<&str, Games> {
-        map(all_consuming(separated_list1(newline, Game::parse)), Games)(s)
+    fn parse(s: &str) -> IResult<'_, Games> {
+        map(
+            all_consuming(separated_list1(
+                newline,
+                cut(context(
+                    "a game like \"Game 1: 3 blue, 4 red; 2 green\"",
+                    Game::parse,
+                )),
+            )),
+            Games,
+        )(s)
     }
 }
 
@@ -95,20 +109,20 @@ impl Game {
         max.red * max.blue * max.green
     }
 
-    fn parse(s: &str) -> IResult<&str, Game> {
+    fn parse(s: &str) -> IResult<'_, Game> {
         map(
             separated_pair(preceded(tag("Game "), u32), tag(": "), Game::parse_grabs),
             |(number, grabs)| Game { number, grabs },
         )(s)
     }
 
-    fn parse_grabs(s: &str) -> IResult<&str, Vec<Grab>> {
+    fn parse_grabs(s: &str) -> IResult<'_, Vec<Grab>> {
         separated_list1(tag("; "), Grab::parse)(s)
     }
 }
 
 impl Grab {
-    fn parse(s: &str) -> IResult<&str, Grab> {
+    fn parse(s: &str) -> IResult<'_, Grab> {
         fold_many1(
             pair(opt(tag(", ")), Grab::parse_grab_color),
             Grab::default,
@@ -124,11 +138,35 @@ impl Grab {
         )(s)
     }
 
-    fn parse_grab_color(s: &str) -> IResult<&str, (u32, &str)> {
+    fn parse_grab_color(s: &str) -> IResult<'_, (u32, &str)> {
         separated_pair(u32, tag(" "), Grab::parse_color)(s)
     }
 
-    fn parse_color(s: &str) -> IResult<&str, &str> {
+    fn parse_color(s: &str) -> IResult<'_, &str> {
         alt((tag("red"), tag("green"), tag("blue")))(s)
     }
 }
+
+struct Generator;
+impl Generator {
+    fn input(rng: &mut Rng, size: usize) -> String {
+        (1..=size)
+            .map(|game| {
+                let grabs = (0..rng.usize(1..=6))
+                    .map(|_| {
+                        let mut colors = ["red", "green", "blue"];
+                        rng.shuffle(&mut colors);
+                        colors[..rng.usize(1..=3)]
+                            .iter()
+                            .map(|color| format!("{} {color}", rng.u32(1..=20)))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                format!("Game {game}: {grabs}")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
@@ -7,6 +7,7 @@ use nom::{
     combinator::map,
     multi::{fold_many1, many1, separated_list1},
 };
+use serde::Serialize;
 
 use super::inputs::{Inputs, INPUTS};
 use crate::prelude::*;
@@ -41,7 +42,7 @@ impl Day for Day03 {
     }
 
     fn part2(parsed: &Self::Parsed) -> Result<Self::Output> {
-        Ok(parsed
+        parsed
             .iter()
             .filter_map(SchematicEnginePart::symbol)
             .filter(|symbol| symbol.symbol == '*')
@@ -55,32 +56,36 @@ impl Day for Day03 {
                 if let Some(first) = iter.next() {
                     if let Some(last) = iter.next() {
                         if iter.next().is_none() {
-                            return Some(first * last);
+                            return Some(
+                                first
+                                    .checked_mul(last)
+                                    .context("The gear ratio should fit in a u32"),
+                            );
                         }
                     }
                 }
 
                 None
             })
-            .sum())
+            .sum()
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Number {
     number: u32,
     x: RangeInclusive<usize>,
     y: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Symbol {
     symbol: char,
     x: usize,
     y: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub enum SchematicEnginePart {
     Number(Number),
     Symbol(Symbol),
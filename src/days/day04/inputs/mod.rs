@@ -1,5 +1,5 @@
 pub type Inputs = [&'static str; 2];
 pub const INPUTS: Inputs = [
-    include_str!("input.txt"),
+    include_str!(concat!(env!("OUT_DIR"), "/day04/input.txt")),
     include_str!("example1.txt"),
 ];
\ No newline at end of file
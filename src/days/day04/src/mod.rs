@@ -2,11 +2,12 @@ use std::collections::HashSet;
 
 use nom::{
     bytes::complete::tag,
-    character::complete::{line_ending, space0, space1, u32, u8},
+    character::complete::{space0, space1, u32, u8},
     combinator::{all_consuming, map},
     multi::separated_list1,
     sequence::{preceded, separated_pair},
 };
+use serde::Serialize;
 
 use super::inputs::{Inputs, INPUTS};
 use crate::prelude::*;
@@ -22,12 +23,16 @@ impl Day for Day04 {
         true
     }
 
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(Generator::input(rng, size))
+    }
+
     fn parse(input: &'static str, _part: Part) -> Result<Self::Parsed> {
         Ok(Parser::input(input)?.1)
     }
 
     fn part1(parsed: &Self::Parsed) -> Result<Self::Output> {
-        Ok(parsed.iter().map(|card| card.points()).sum())
+        parsed.iter().map(|card| card.points()).sum()
     }
 
     fn part2(parsed: &Self::Parsed) -> Result<Self::Output> {
@@ -35,14 +40,17 @@ impl Day for Day04 {
         for (i, card) in parsed.iter().enumerate() {
             // i is 1-based and card.number is 1-based, so is already the next card. And it is the right type: u32
             for number in card.number..card.number + card.wins() {
-                count[number as usize] += count[i];
+                let copies = count[i];
+                *count
+                    .get_mut(number as usize)
+                    .context("Cards should never be copied past the end of the table")? += copies;
             }
         }
         Ok(count.into_iter().sum())
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Card {
     number: u32,
     winning: HashSet<u8>,
@@ -50,12 +58,13 @@ pub struct Card {
 }
 
 impl Card {
-    pub fn points(&self) -> u32 {
+    pub fn points(&self) -> Result<u32> {
         let wins = self.wins();
         if wins == 0 {
-            0
+            Ok(0)
         } else {
-            2u32.pow(wins - 1)
+            2u32.checked_pow(wins - 1)
+                .with_context(|| format!("Card {} is worth too many points", self.number))
         }
     }
 
@@ -67,7 +76,10 @@ impl Card {
 struct Parser;
 impl Parser {
     fn input(s: &str) -> IResult<Vec<Card>> {
-        all_consuming(separated_list1(line_ending, Parser::line))(s)
+        all_consuming(lines(
+            "a card like \"Card 1: 41 48 | 83 86 17\"",
+            Parser::line,
+        ))(s)
     }
 
     fn line(s: &str) -> IResult<Card> {
@@ -89,3 +101,31 @@ impl Parser {
         preceded(space0, separated_list1(space1, u8))(s)
     }
 }
+
+struct Generator;
+impl Generator {
+    /// Cards never win copies of cards past the end of the table.
+    fn input(rng: &mut Rng, size: usize) -> String {
+        (1..=size)
+            .map(|card| {
+                let mut numbers: Vec<u8> = (1..100).collect();
+                rng.shuffle(&mut numbers);
+                let (winning, mine) = numbers.split_at_mut(5);
+                let mine = &mut mine[..8];
+                let wins = rng.usize(0..=(size - card).min(5));
+                mine[..wins].copy_from_slice(&winning[..wins]);
+                rng.shuffle(mine);
+
+                let format = |numbers: &[u8]| {
+                    numbers
+                        .iter()
+                        .map(|n| format!("{n:2}"))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                };
+                format!("Card {card:3}: {} | {}", format(winning), format(mine))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
@@ -11,6 +11,7 @@ use nom::{
     multi::separated_list1,
     sequence::{pair, preceded, separated_pair},
 };
+use serde::Serialize;
 
 use super::inputs::{Inputs, INPUTS};
 use crate::prelude::*;
@@ -26,6 +27,10 @@ impl Day for Day05 {
         true
     }
 
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(Generator::input(rng, size))
+    }
+
     fn parse(input: &'static str, _part: Part) -> Result<Self::Parsed> {
         Ok(Parser::input(input)?.1)
     }
@@ -48,6 +53,10 @@ impl Day for Day05 {
     }
 
     fn part2(parsed: &Self::Parsed) -> Result<Self::Output> {
+        ensure!(
+            parsed.seeds.len() % 2 == 0,
+            "The seeds should be pairs of a start and a length"
+        );
         let mut ranges = parsed
             .seeds
             .chunks(2)
@@ -71,19 +80,19 @@ impl Day for Day05 {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Almanac {
     seeds: Vec<i64>,
     maps: HashMap<&'static str, Map>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Map {
     destination: &'static str,
     mappings: Mappings<i64>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Mappings<T: Debug>(Vec<(Range<T>, T)>);
 
 impl<T: Debug + Copy + Ord> FromIterator<(Range<T>, T)> for Mappings<T> {
@@ -215,3 +224,95 @@ impl Parser {
         )(s)
     }
 }
+
+struct Generator;
+impl Generator {
+    fn input(rng: &mut Rng, size: usize) -> String {
+        const CATEGORIES: [&str; 8] = [
+            "seed",
+            "soil",
+            "fertilizer",
+            "water",
+            "light",
+            "temperature",
+            "humidity",
+            "location",
+        ];
+
+        let seeds = (0..rng.usize(1..=size.min(4)))
+            .map(|_| {
+                format!(
+                    "{} {}",
+                    rng.u32(0..10 * size as u32),
+                    rng.u32(1..=size as u32 + 1)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let maps = CATEGORIES[..rng.usize(2..=CATEGORIES.len())]
+            .windows(2)
+            .map(|categories| {
+                let mut start = 0;
+                let mut mappings = (0..rng.usize(1..=size.min(5)))
+                    .map(|_| {
+                        start += rng.u32(0..=size as u32);
+                        let length = rng.u32(1..=size as u32 + 1);
+                        let mapping = format!("{} {start} {length}", rng.u32(0..10 * size as u32));
+                        start += length;
+                        mapping
+                    })
+                    .collect::<Vec<_>>();
+                rng.shuffle(&mut mappings);
+                format!(
+                    "{}-to-{} map:\n{}",
+                    categories[0],
+                    categories[1],
+                    mappings.join("\n")
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        format!("seeds: {seeds}\n\n{maps}")
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn map_range_agrees_with_map() {
+    use std::collections::BTreeSet;
+
+    crate::property::check(20, Generator::input, |input| {
+        let almanac = Day05::parse(crate::property::leak(input.clone()), Part2)?;
+        for map in almanac.maps.values() {
+            for seeds in almanac.seeds.chunks(2) {
+                let range = seeds[0]..seeds[0] + seeds[1];
+                let expected = range
+                    .clone()
+                    .map(|seed| map.mappings.map(seed))
+                    .collect::<BTreeSet<_>>();
+                let actual = map
+                    .mappings
+                    .map_range(&range)
+                    .into_iter()
+                    .flatten()
+                    .collect::<BTreeSet<_>>();
+                ensure!(
+                    expected == actual,
+                    "Mapping {range:?} gives {actual:?}, expected {expected:?}"
+                );
+            }
+        }
+
+        let seeds = almanac
+            .seeds
+            .chunks(2)
+            .flat_map(|seeds| seeds[0]..seeds[0] + seeds[1])
+            .collect();
+        let expected = Day05::part1(&Almanac { seeds, ..almanac })?;
+        let almanac = Day05::parse(crate::property::leak(input.clone()), Part2)?;
+        ensure!(Day05::part2(&almanac)? == expected);
+        Ok(())
+    });
+}
@@ -2,9 +2,11 @@ use nom::{
     bytes::complete::{is_not, tag},
     character::complete::{line_ending, space1, u64},
     combinator::{all_consuming, map, map_res},
+    error::context,
     multi::separated_list1,
     sequence::{preceded, separated_pair},
 };
+use serde::Serialize;
 
 use super::inputs::{Inputs, INPUTS};
 use crate::prelude::*;
@@ -25,31 +27,41 @@ impl Day for Day06 {
     }
 
     fn part1(parsed: &Self::Parsed) -> Result<Self::Output> {
-        Ok(parsed.iter().map(Race::wins).product())
+        parsed.iter().map(Race::wins).product()
     }
 
     fn part2(parsed: &Self::Parsed) -> Result<Self::Output> {
-        Ok(parsed.iter().map(Race::wins).product())
+        parsed.iter().map(Race::wins).product()
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Race {
     time: u64,
     distance: u64,
 }
 
 impl Race {
-    pub fn wins(&self) -> u64 {
-        let d = ((self.time.pow(2) - 4 * self.distance) as f64).sqrt();
+    pub fn wins(&self) -> Result<u64> {
+        let (Some(time_squared), Some(distance_times_four)) =
+            (self.time.checked_pow(2), self.distance.checked_mul(4))
+        else {
+            bail!("The race of {} ms is too long", self.time);
+        };
+        // Without a real solution, the record can't be beaten
+        let Some(discriminant) = time_squared.checked_sub(distance_times_four) else {
+            return Ok(0);
+        };
+
+        let d = (discriminant as f64).sqrt();
         let first = Race::ceil((-1f64 * self.time as f64 + d) / -2f64);
         let last = Race::floor((-1f64 * self.time as f64 - d) / -2f64);
-        last - first + 1
+        Ok((last - first + 1).max(0) as u64)
     }
 
-    fn floor(i: f64) -> u64 {
-        let f = i.floor() as u64;
-        let c = i.ceil() as u64;
+    fn floor(i: f64) -> i64 {
+        let f = i.floor() as i64;
+        let c = i.ceil() as i64;
         if f == c {
             f - 1
         } else {
@@ -57,9 +69,9 @@ impl Race {
         }
     }
 
-    fn ceil(i: f64) -> u64 {
-        let f = i.floor() as u64;
-        let c = i.ceil() as u64;
+    fn ceil(i: f64) -> i64 {
+        let f = i.floor() as i64;
+        let c = i.ceil() as i64;
         if f == c {
             c + 1
         } else {
@@ -73,9 +85,9 @@ impl Parser {
     fn input(s: &'static str, part: Part) -> IResult<Vec<Race>> {
         map(
             all_consuming(separated_pair(
-                Parser::line("Time:", part),
+                context("\"Time:\" and numbers", Parser::line("Time:", part)),
                 line_ending,
-                Parser::line("Distance:", part),
+                context("\"Distance:\" and numbers", Parser::line("Distance:", part)),
             )),
             |(times, distances)| {
                 times
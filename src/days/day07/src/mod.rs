@@ -2,11 +2,11 @@ use std::cmp::Ordering;
 
 use nom::{
     branch::alt,
-    character::complete::{char, line_ending, space1, u64},
+    character::complete::{char, space1, u64},
     combinator::{all_consuming, map},
-    multi::separated_list1,
     sequence::{separated_pair, tuple},
 };
+use serde::Serialize;
 
 use super::inputs::{Inputs, INPUTS};
 use crate::prelude::*;
@@ -22,6 +22,10 @@ impl Day for Day07 {
         true
     }
 
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(Generator::input(rng, size))
+    }
+
     fn parse(input: &'static str, _part: Part) -> Result<Self::Parsed> {
         Ok(Parser::input(input)?.1)
     }
@@ -57,7 +61,7 @@ impl Day07 {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
 #[repr(u64)]
 pub enum Card {
     Two = 2,
@@ -194,7 +198,10 @@ impl HandType {
 struct Parser;
 impl Parser {
     fn input(s: &'static str) -> IResult<Vec<([Card; 5], Bid)>> {
-        all_consuming(separated_list1(line_ending, Parser::hand_bid))(s)
+        all_consuming(lines(
+            "five cards of \"23456789TJQKA\" and a bid",
+            Parser::hand_bid,
+        ))(s)
     }
 
     fn hand_bid(s: &'static str) -> IResult<([Card; 5], Bid)> {
@@ -232,3 +239,20 @@ impl Parser {
         ))(s)
     }
 }
+
+struct Generator;
+impl Generator {
+    fn input(rng: &mut Rng, size: usize) -> String {
+        const CARDS: [char; 13] = [
+            '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A',
+        ];
+
+        (0..size)
+            .map(|_| {
+                let hand: String = (0..5).map(|_| CARDS[rng.usize(..CARDS.len())]).collect();
+                format!("{hand} {}", rng.u32(1..=1000))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
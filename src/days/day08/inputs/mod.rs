@@ -1,6 +1,6 @@
 pub type Inputs = [&'static str; 4];
 pub const INPUTS: Inputs = [
-    include_str!("input.txt"),
+    include_str!(concat!(env!("OUT_DIR"), "/day08/input.txt")),
     include_str!("example1.txt"),
     include_str!("example2.txt"),
     include_str!("example3.txt"),
@@ -3,12 +3,12 @@ use std::{collections::HashMap, iter::repeat};
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{alphanumeric1, line_ending, multispace1},
+    character::complete::{alphanumeric1, multispace1},
     combinator::{all_consuming, map},
-    multi::{many1, separated_list1},
     sequence::{delimited, separated_pair},
 };
 use num_integer::Integer;
+use serde::Serialize;
 
 use super::inputs::{Inputs, INPUTS};
 use crate::prelude::*;
@@ -32,24 +32,63 @@ impl Day for Day08 {
     }
 
     fn part1(parsed: &Self::Parsed) -> Result<Self::Output> {
-        Ok(Self::steps(parsed, "AAA", |node| node == "ZZZ"))
+        Self::steps(parsed, "AAA", |node| node == "ZZZ")
     }
 
     fn part2(parsed: &Self::Parsed) -> Result<Self::Output> {
-        Ok(parsed
+        parsed
             .1
             .iter()
-            .filter_map(|(start_node, _)| {
-                if start_node.chars().nth(2).unwrap() == 'A' {
-                    let start_to_end = Self::steps(parsed, start_node, |node| {
-                        node.chars().nth(2).unwrap() == 'Z'
-                    });
-                    Some(start_to_end)
-                } else {
-                    None
-                }
-            })
-            .fold(parsed.0.len(), |lcm, ghost_step| lcm.lcm(&ghost_step)))
+            .filter(|(start_node, _)| start_node.ends_with('A'))
+            .map(|(start_node, _)| Self::steps(parsed, start_node, |node| node.ends_with('Z')))
+            .try_fold(
+                parsed.0.len(),
+                |lcm, ghost_steps| Ok(lcm.lcm(&ghost_steps?)),
+            )
+    }
+
+    fn validate(parsed: &Self::Parsed) -> Vec<Violation> {
+        let map = parsed.1.iter().copied().collect::<HashMap<_, _>>();
+        let mut violations = Vec::new();
+
+        for node in ["AAA", "ZZZ"] {
+            if !map.contains_key(node) {
+                violations.push(Violation::new(
+                    Part1,
+                    "The network contains AAA and ZZZ",
+                    format!("Node {node} is not found"),
+                ));
+            }
+        }
+
+        let is_end_node = |node: Node| node.ends_with('Z');
+        for start_node in map.keys().filter(|node| node.ends_with('A')) {
+            let Some((steps, end_node)) = Self::walk(parsed, &map, start_node, 0, is_end_node)
+            else {
+                violations.push(Violation::new(
+                    Part2,
+                    "Every ghost reaches an end node",
+                    format!("Ghost starting at {start_node} never reaches a node ending with Z"),
+                ));
+                continue;
+            };
+
+            let cycle = Self::walk(parsed, &map, end_node, steps, is_end_node);
+            if cycle != Some((steps, end_node)) {
+                violations.push(Violation::new(
+                    Part2,
+                    "Every ghost cycles back to its first end node in the same number of steps",
+                    format!(
+                        "Ghost starting at {start_node} reaches {end_node} after {steps} steps, \
+                         but then reaches {} after {} steps",
+                        cycle.map_or("nothing", |(_, node)| node),
+                        cycle.map_or(0, |(steps, _)| steps),
+                    ),
+                ));
+            }
+        }
+
+        violations
     }
 }
 
@@ -58,26 +97,54 @@ impl Day08 {
         parsed: &<Self as Day>::Parsed,
         start_node: Node,
         is_end_node: impl Fn(Node) -> bool,
-    ) -> <Self as Day>::Output {
+    ) -> Result<<Self as Day>::Output> {
         let map = parsed.1.iter().copied().collect::<HashMap<_, _>>();
         let mut instructions = repeat(parsed.0.iter()).flatten();
         let mut current = start_node;
         let mut steps = 0;
         while !is_end_node(current) || steps == 0 {
+            // After visiting every node at every instruction, the walk only repeats itself
+            ensure!(
+                steps <= map.len() * parsed.0.len(),
+                "Starting at node {start_node} an end node is never reached"
+            );
             let map_item = map
                 .get(current)
-                .context(format!("Node {current} is not found in the network"))
-                .unwrap();
-            current = match instructions.next().unwrap() {
+                .context(format!("Node {current} is not found in the network"))?;
+            current = match instructions.next().context("There are no instructions")? {
                 Instruction::Left => map_item.0,
                 Instruction::Right => map_item.1,
             };
             steps += 1;
         }
-        steps
+        Ok(steps)
+    }
+
+    /// Walk from `start_node` starting with instruction `offset` until an end node is reached. Gives
+    /// up when a node is missing, or when every node is visited at every instruction.
+    fn walk<'a>(
+        parsed: &<Self as Day>::Parsed,
+        map: &HashMap<Node<'a>, (Node<'a>, Node<'a>)>,
+        start_node: Node<'a>,
+        offset: usize,
+        is_end_node: impl Fn(Node) -> bool,
+    ) -> Option<(usize, Node<'a>)> {
+        let mut current = start_node;
+        for steps in 1..=map.len() * parsed.0.len() {
+            let map_item = map.get(current)?;
+            current = match parsed.0[(offset + steps - 1) % parsed.0.len()] {
+                Instruction::Left => map_item.0,
+                Instruction::Right => map_item.1,
+            };
+            if is_end_node(current) {
+                return Some((steps, current));
+            }
+        }
+        None
     }
 }
 
+#[derive(Serialize)]
 pub enum Instruction {
     Left,
     Right,
@@ -96,14 +163,17 @@ impl Parser {
     }
 
     fn instructions(s: &'static str) -> IResult<Vec<Instruction>> {
-        many1(alt((
-            map(tag("L"), |_| Instruction::Left),
-            map(tag("R"), |_| Instruction::Right),
-        )))(s)
+        tiles(
+            "an instruction 'L' or 'R'",
+            alt((
+                map(tag("L"), |_| Instruction::Left),
+                map(tag("R"), |_| Instruction::Right),
+            )),
+        )(s)
     }
 
     fn network(s: &'static str) -> IResult<Vec<(Node, (Node, Node))>> {
-        separated_list1(line_ending, Parser::map_item)(s)
+        lines("a node like \"AAA = (BBB, CCC)\"", Parser::map_item)(s)
     }
 
     fn map_item(s: &'static str) -> IResult<(Node, (Node, Node))> {
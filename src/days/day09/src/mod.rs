@@ -1,5 +1,5 @@
 use nom::{
-    character::complete::{i64, line_ending, space1},
+    character::complete::{i64, space1},
     combinator::all_consuming,
     multi::separated_list1,
 };
@@ -18,6 +18,10 @@ impl Day for Day09 {
         true
     }
 
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(Generator::input(rng, size))
+    }
+
     fn parse(input: &'static str, _part: Part) -> Result<Self::Parsed> {
         Ok(Parser::input(input)?.1)
     }
@@ -61,10 +65,12 @@ impl Day for Day09 {
                         })
                         .unwrap_or_default();
                     history = next_history;
+                    // Like in part 1, an empty history counts as zeros
+                    let first = history.first().copied().unwrap_or_default();
                     if subtract {
-                        predicted -= history[0];
+                        predicted -= first;
                     } else {
-                        predicted += history[0];
+                        predicted += first;
                     }
                     subtract = !subtract;
                 }
@@ -77,10 +83,34 @@ impl Day for Day09 {
 struct Parser;
 impl Parser {
     fn input(s: &'static str) -> IResult<<Day09 as Day>::Parsed> {
-        all_consuming(separated_list1(line_ending, Parser::history))(s)
+        all_consuming(lines("numbers separated by spaces", Parser::history))(s)
     }
 
     fn history(s: &'static str) -> IResult<Vec<<Day09 as Day>::Output>> {
         separated_list1(space1, i64)(s)
     }
 }
+
+struct Generator;
+impl Generator {
+    /// Histories are values of polynomials, so the differences always end up all zeros.
+    fn input(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                let coefficients: Vec<i64> =
+                    (0..rng.usize(1..=4)).map(|_| rng.i64(-9..=9)).collect();
+                (0..21)
+                    .map(|x| {
+                        coefficients
+                            .iter()
+                            .rev()
+                            .fold(0, |value, coefficient| value * x + coefficient)
+                            .to_string()
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
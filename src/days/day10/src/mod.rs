@@ -1,5 +1,7 @@
 use std::iter::repeat;
 
+use serde::Serialize;
+
 use super::inputs::{Inputs, INPUTS};
 use crate::prelude::*;
 
@@ -15,50 +17,81 @@ impl Day for Day10 {
     }
 
     fn parse(input: &'static str, _part: Part) -> Result<Self::Parsed> {
-        let mut start = (0, 0);
+        let mut start = None;
         let map = input
             .lines()
             .enumerate()
             .map(|(y, line)| {
                 line.chars()
                     .enumerate()
-                    .map(|(x, c)| {
-                        if c == 'S' {
-                            start = (y as i32, x as i32);
-                            None
-                        } else {
-                            Some(c)
+                    .map(|(x, c)| match c {
+                        'S' => {
+                            ensure!(start.is_none(), "There should be only one start");
+                            start = Some((y as i32, x as i32));
+                            Ok(None)
                         }
+                        '|' | '-' | 'L' | 'J' | '7' | 'F' | '.' => Ok(Some(c)),
+                        // Examples mark ground inside and outside of the loop
+                        'I' | 'O' => Ok(Some('.')),
+                        _ => bail!(
+                            "Unknown character {c:?} at line {}, column {}",
+                            y + 1,
+                            x + 1
+                        ),
                     })
                     .collect()
             })
-            .collect();
+            .collect::<Result<_>>()?;
 
-        Ok(Map { map, start })
+        Ok(Map {
+            map,
+            start: start.context("There should be a start")?,
+        })
     }
 
     fn part1(parsed: &Self::Parsed) -> Result<Self::Output> {
         let mut map = parsed.clone();
-        Ok(Day10::bfs_loop(&mut map))
+        Day10::bfs_loop(&mut map)
     }
 
     fn part2(parsed: &Self::Parsed) -> Result<Self::Output> {
         let mut map = parsed.double();
-        Day10::bfs_loop(&mut map);
+        Day10::bfs_loop(&mut map)?;
         map.flood_borders();
         Ok(map.undouble().len())
     }
+
+    fn visualize(parsed: &Self::Parsed, part: Part) -> Result<Option<Box<dyn Visualize + '_>>> {
+        let mut outside_loop = parsed.clone();
+        Day10::bfs_loop(&mut outside_loop)?;
+
+        let inside = match part {
+            Part1 => None,
+            Part2 => {
+                let mut map = parsed.double();
+                Day10::bfs_loop(&mut map)?;
+                map.flood_borders();
+                Some(map.undouble())
+            }
+        };
+
+        Ok(Some(Box::new(Loop {
+            map: parsed,
+            outside_loop,
+            inside,
+        })))
+    }
 }
 
 impl Day10 {
-    fn bfs_loop(map: &mut Map) -> i32 {
+    fn bfs_loop(map: &mut Map) -> Result<i32> {
         let mut pipes = vec![(map.start, 'S')];
         let mut depth = -1;
         while !pipes.is_empty() {
             depth += 1;
             pipes = pipes
                 .into_iter()
-                .fold(Vec::new(), |mut pipes, ((y, x), c)| {
+                .try_fold(Vec::new(), |mut pipes, ((y, x), c)| {
                     match c {
                         'S' => {
                             map.move_left(y, x, &mut pipes);
@@ -91,23 +124,68 @@ impl Day10 {
                             map.move_right(y, x, &mut pipes);
                         }
                         '.' => {}
-                        _ => {
-                            panic!("Unknown character {c}");
-                        }
+                        _ => bail!("Unknown character {c:?}"),
                     }
-                    pipes
-                });
+                    Ok(pipes)
+                })?;
         }
-        depth
+        Ok(depth)
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize)]
 pub struct Map {
     map: Vec<Vec<Option<char>>>,
     start: (i32, i32),
 }
 
+/// The loop, and for part 2 the tiles enclosed by it, for `--visualize`. Solving takes the tiles
+/// of the loop and of the outside from a map, so what is left tells where a tile is.
+struct Loop<'a> {
+    map: &'a Map,
+    outside_loop: Map,
+    inside: Option<Map>,
+}
+
+impl Visualize for Loop<'_> {
+    fn visualize(&self) -> String {
+        let width = self.map.map.first().map_or(0, Vec::len);
+        grid(width, self.map.map.len(), |x, y| {
+            let tile = |map: &Map| {
+                map.map
+                    .get(y)
+                    .and_then(|line| line.get(x))
+                    .copied()
+                    .flatten()
+            };
+            if (y as i32, x as i32) == self.map.start {
+                return paint('S', Color::Red);
+            }
+            let c = tile(self.map).unwrap_or(' ');
+            if tile(&self.outside_loop).is_none() {
+                let pipe = match c {
+                    '|' => '│',
+                    '-' => '─',
+                    'L' => '└',
+                    'J' => '┘',
+                    '7' => '┐',
+                    'F' => '┌',
+                    c => c,
+                };
+                paint(pipe, Color::Yellow)
+            } else if self
+                .inside
+                .as_ref()
+                .is_some_and(|inside| tile(inside).is_some())
+            {
+                paint('I', Color::Green)
+            } else {
+                paint(c, Color::Gray)
+            }
+        })
+    }
+}
+
 impl Map {
     pub fn move_left(&mut self, y: i32, x: i32, pipes: &mut Vec<((i32, i32), char)>) {
         self.move_checked(y, x - 1, pipes, "-LF")
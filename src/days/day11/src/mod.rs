@@ -1,5 +1,7 @@
 use std::{cell::RefCell, rc::Rc};
 
+use serde::Serialize;
+
 use super::inputs::{Inputs, INPUTS};
 use crate::prelude::*;
 
@@ -14,20 +16,26 @@ impl Day for Day11 {
         true
     }
 
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(Generator::input(rng, size))
+    }
+
     fn parse(input: &'static str, _part: Part) -> Result<Self::Parsed> {
-        Ok(input
+        input
             .lines()
             .enumerate()
             .flat_map(|(y, line)| {
-                line.chars().enumerate().filter_map(move |(x, c)| {
-                    if c == '#' {
-                        Some(Galaxy([y, x]))
-                    } else {
-                        None
-                    }
+                line.chars().enumerate().filter_map(move |(x, c)| match c {
+                    '#' => Some(Ok(Galaxy([y, x]))),
+                    '.' => None,
+                    _ => Some(Err(anyhow!(
+                        "Unknown character {c:?} at line {}, column {}",
+                        y + 1,
+                        x + 1
+                    ))),
                 })
             })
-            .collect())
+            .collect()
     }
 
     fn part1(parsed: &Self::Parsed) -> Result<Self::Output> {
@@ -46,7 +54,7 @@ impl Day for Day11 {
 #[derive(Clone)]
 pub struct Image(Vec<Rc<RefCell<Galaxy>>>);
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize)]
 pub struct Galaxy([usize; 2]);
 
 pub const Y: usize = 0;
@@ -70,6 +78,10 @@ impl Image {
     }
 
     pub fn expand_key(&self, key: usize, times: usize) {
+        if self.0.is_empty() {
+            return;
+        }
+
         let mut sorted = self.0.clone();
         sorted.sort_by_key(|galaxy| galaxy.borrow().0[key]);
         let mut i = 0;
@@ -102,3 +114,17 @@ impl Galaxy {
         self.0[X].abs_diff(other.0[X]) + self.0[Y].abs_diff(other.0[Y])
     }
 }
+
+struct Generator;
+impl Generator {
+    fn input(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| if rng.u8(0..10) == 0 { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
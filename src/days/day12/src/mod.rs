@@ -1,6 +1,6 @@
 use nom::{
-    bytes::complete::{is_not, tag},
-    character::complete::{line_ending, u64},
+    bytes::complete::{is_a, tag},
+    character::complete::u64,
     combinator::{all_consuming, map},
     multi::separated_list1,
     sequence::separated_pair,
@@ -24,6 +24,10 @@ impl Day for Day12 {
         true
     }
 
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(Generator::input(rng, size))
+    }
+
     fn parse(input: &'static str, _part: Part) -> Result<Self::Parsed> {
         Ok(Parser::input(input)?.1)
     }
@@ -87,6 +91,7 @@ fn solve(springs: String, groups: Vec<usize>) -> <Day12 as Day>::Output {
         '#' => pound(),
         '.' => dot(),
         '?' => pound() + dot(),
+        // The parser only accepts the characters above
         _ => unreachable!(),
     }
 }
@@ -94,17 +99,46 @@ fn solve(springs: String, groups: Vec<usize>) -> <Day12 as Day>::Output {
 struct Parser;
 impl Parser {
     fn input(s: &str) -> IResult<<Day12 as Day>::Parsed> {
-        all_consuming(separated_list1(
-            line_ending,
+        all_consuming(lines(
+            "springs and groups like \"???.### 1,1,3\"",
             separated_pair(Parser::springs, tag(" "), Parser::groups),
         ))(s)
     }
 
     fn springs(s: &str) -> IResult<String> {
-        map(is_not(" "), String::from)(s)
+        map(is_a("?.#"), String::from)(s)
     }
 
     fn groups(s: &str) -> IResult<Vec<usize>> {
         separated_list1(tag(","), map(u64, |i| i as usize))(s)
     }
 }
+
+struct Generator;
+impl Generator {
+    /// Rows of springs with a known arrangement, of which some springs are unknown.
+    fn input(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                let mut springs: Vec<char> = (0..rng.usize(1..=20))
+                    .map(|_| if rng.bool() { '#' } else { '.' })
+                    .collect();
+                // There is at least one group of damaged springs
+                let damaged = rng.usize(..springs.len());
+                springs[damaged] = '#';
+
+                let groups: Vec<_> = springs
+                    .split(|c| *c == '.')
+                    .filter(|group| !group.is_empty())
+                    .map(|group| group.len().to_string())
+                    .collect();
+                let springs: String = springs
+                    .into_iter()
+                    .map(|c| if rng.u8(0..3) == 0 { '?' } else { c })
+                    .collect();
+                format!("{springs} {}", groups.join(","))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
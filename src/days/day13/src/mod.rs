@@ -7,9 +7,11 @@ use std::{
 use nom::{
     branch::alt,
     character::complete::{char, line_ending},
-    combinator::{all_consuming, map},
-    multi::{many1, many_m_n, separated_list1},
+    combinator::{all_consuming, map, verify},
+    error::context,
+    multi::{many_m_n, separated_list1},
 };
+use serde::Serialize;
 
 use super::inputs::{Inputs, INPUTS};
 use crate::prelude::*;
@@ -46,6 +48,7 @@ impl Day for Day13 {
 
 pub type Pattern = Vec<Vec<char>>;
 
+#[derive(Serialize)]
 pub struct HashedPattern {
     rows: Vec<(String, u64)>,
     columns: Vec<(String, u64)>,
@@ -94,7 +97,8 @@ impl HashedPattern {
             let mut error_found = false;
             if lines[0..i].iter().rev().zip(lines[i..].iter()).all(
                 |((left_string, left_hash), (right_string, right_hash))| {
-                    if left_hash == right_hash {  // If hashes do match: continue
+                    if left_hash == right_hash {
+                        // If hashes do match: continue
                         true
                     } else if part == Part1       // We are in part 1 and hashes did not match: stop
                         || error_found            // If hashes did not match and we had found an error earlier: stop
@@ -116,8 +120,8 @@ impl HashedPattern {
                     {
                         false
                     } else {
-                        error_found = true;      // If hashes did not match, but strings where different on exactly 1 character,
-                        true                     // and we didn't stop yet: register we found an error and continue
+                        error_found = true; // If hashes did not match, but strings where different on exactly 1 character,
+                        true // and we didn't stop yet: register we found an error and continue
                     }
                 },
             ) && (part == Part1 || error_found)
@@ -140,12 +144,18 @@ impl Parser {
 
     fn pattern(s: &str) -> IResult<HashedPattern> {
         map(
-            separated_list1(line_ending, Parser::line),
+            context(
+                "rows of the same length",
+                verify(
+                    separated_list1(line_ending, Parser::line),
+                    |rows: &Pattern| rows.iter().all(|row| row.len() == rows[0].len()),
+                ),
+            ),
             HashedPattern::hash,
         )(s)
     }
 
     fn line(s: &str) -> IResult<Vec<char>> {
-        many1(alt((char('.'), char('#'))))(s)
+        tiles("a tile '.' or '#'", alt((char('.'), char('#'))))(s)
     }
 }
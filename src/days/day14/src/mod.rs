@@ -3,8 +3,9 @@ use std::collections::HashMap;
 use nom::{
     branch::alt,
     character::complete::{char, line_ending},
-    combinator::all_consuming,
-    multi::{many1, separated_list1},
+    combinator::{all_consuming, verify},
+    error::context,
+    multi::separated_list1,
 };
 
 use super::inputs::{Inputs, INPUTS};
@@ -21,6 +22,10 @@ impl Day for Day14 {
         true
     }
 
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(Generator::input(rng, size))
+    }
+
     fn parse(input: &'static str, _part: Part) -> Result<Self::Parsed> {
         Ok(Parser::input(input)?.1)
     }
@@ -33,15 +38,41 @@ impl Day for Day14 {
 
     fn part2(parsed: &Self::Parsed) -> Result<Self::Output> {
         let mut platform = Platform::from(parsed);
-        for i in 1..1_000_000_000 {
-            if let Some(cycle_length) = platform.cycle(i) {
-                for _ in 0..(1_000_000_000 - i) % cycle_length {
-                    platform.cycle(0);
-                }
-                return Ok(platform.total_load());
+        platform.spin(&mut ())?;
+        Ok(platform.total_load())
+    }
+
+    fn observe(
+        parsed: &Self::Parsed,
+        part: Part,
+        observer: &mut dyn Observer,
+    ) -> Result<Option<Self::Output>> {
+        Ok(match part {
+            Part1 => None,
+            Part2 => {
+                let mut platform = Platform::from(parsed);
+                platform.spin(observer)?;
+                Some(platform.total_load())
             }
+        })
+    }
+
+    fn visualize(parsed: &Self::Parsed, part: Part) -> Result<Option<Box<dyn Visualize + '_>>> {
+        let mut platform = Platform::from(parsed);
+        match part {
+            Part1 => platform.tilt_north(),
+            Part2 => platform.spin(&mut ())?,
         }
-        Err(anyhow!("We should have found the cycle by now"))
+        Ok(Some(Box::new(platform)))
+    }
+
+    fn render(parsed: &Self::Parsed, part: Part) -> Result<Option<Box<dyn Render + '_>>> {
+        let mut platform = Platform::from(parsed);
+        match part {
+            Part1 => platform.tilt_north(),
+            Part2 => platform.spin(&mut ())?,
+        }
+        Ok(Some(Box::new(platform)))
     }
 }
 
@@ -171,6 +202,12 @@ impl Platform {
         if let Some(prev) = self.cache.get(&self.rocks).copied() {
             self.cache.clear();
             self.cache_hits += 1;
+            tracing::debug!(
+                cycle = i,
+                previous = prev,
+                hits = self.cache_hits,
+                "cache hit"
+            );
             if self.cache_hits == 2 {
                 return Some(i - prev);
             }
@@ -180,6 +217,29 @@ impl Platform {
         None
     }
 
+    /// Run the billion spin cycles, skipping the repeated ones.
+    pub fn spin<O: Observer + ?Sized>(&mut self, observer: &mut O) -> Result<()> {
+        for i in 1..1_000_000_000 {
+            let cycle_length = self.cycle(i);
+            self.observe(i, observer);
+            if let Some(cycle_length) = cycle_length {
+                for j in 1..=(1_000_000_000 - i) % cycle_length {
+                    self.cycle(0);
+                    self.observe(i + j, observer);
+                }
+                return Ok(());
+            }
+        }
+        Err(anyhow!("We should have found the cycle by now"))
+    }
+
+    fn observe<O: Observer + ?Sized>(&self, cycle: usize, observer: &mut O) {
+        observer.observe(&Event::SpinCycle {
+            cycle,
+            load: self.total_load(),
+        });
+    }
+
     pub fn total_load(&self) -> <Day14 as Day>::Output {
         self.rocks
             .iter()
@@ -191,40 +251,75 @@ impl Platform {
     }
 }
 
-#[cfg(test)]
-impl std::fmt::Debug for Platform {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.size {
-            for x in 1..self.size + 1 {
-                if let Some(rock) = self
-                    .rocks
-                    .iter()
-                    .find(|rock| rock.x == x && rock.y == self.size - y)
-                {
-                    match rock.shape {
-                        RockShape::Square => {
-                            write!(f, "#")?;
-                        }
-                        RockShape::Round => {
-                            write!(f, "O")?;
-                        }
-                    }
-                } else {
-                    write!(f, ".")?;
-                }
+struct Generator;
+impl Generator {
+    fn input(rng: &mut Rng, size: usize) -> String {
+        let size = size.min(254);
+        (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| match rng.u8(0..10) {
+                        0..=1 => 'O',
+                        2 => '#',
+                        _ => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl Visualize for Platform {
+    fn visualize(&self) -> String {
+        let size = self.size as usize;
+        grid(size, size, |x, y| {
+            let rock = self
+                .rocks
+                .iter()
+                .find(|rock| rock.x as usize == x + 1 && rock.y as usize == size - y);
+            match rock.map(|rock| rock.shape) {
+                Some(RockShape::Square) => paint('#', Color::Gray),
+                Some(RockShape::Round) => paint('O', Color::Yellow),
+                None => ".".to_string(),
             }
-            writeln!(f)?;
+        })
+    }
+}
+
+impl Render for Platform {
+    fn render(&self) -> Image {
+        let size = self.size as usize;
+        let mut image = Image::new(size as f64, size as f64);
+        for rock in &self.rocks {
+            let color = match rock.shape {
+                RockShape::Square => Rgb::GRAY,
+                RockShape::Round => Rgb::YELLOW,
+            };
+            image.tile(rock.x as usize - 1, size - rock.y as usize, color);
         }
-        Ok(())
+        image
     }
 }
 
 struct Parser;
 impl Parser {
     fn input(s: &'static str) -> IResult<<Day14 as Day>::Parsed> {
-        all_consuming(separated_list1(
-            line_ending,
-            many1(alt((char('#'), char('O'), char('.')))),
+        all_consuming(context(
+            "a square platform of at most 254 rows",
+            verify(
+                separated_list1(
+                    line_ending,
+                    tiles(
+                        "a tile '#', 'O' or '.'",
+                        alt((char('#'), char('O'), char('.'))),
+                    ),
+                ),
+                |rows: &<Day14 as Day>::Parsed| {
+                    // Positions are stored in a u8, with room for the borders
+                    rows.len() < u8::MAX as usize && rows.iter().all(|row| row.len() == rows.len())
+                },
+            ),
         ))(s)
     }
 }
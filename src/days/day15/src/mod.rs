@@ -2,7 +2,8 @@ use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{alpha1, char, u8},
-    combinator::{all_consuming, opt},
+    combinator::{all_consuming, cut, opt},
+    error::context,
     multi::separated_list1,
     sequence::tuple,
 };
@@ -13,6 +14,18 @@ use crate::prelude::*;
 pub struct Day15;
 impl Day for Day15 {
     const INPUTS: Self::Inputs = INPUTS;
+    #[cfg(test)]
+    const INPUT_SPECIFIC: &'static [InputSpecific] = &[InputSpecific {
+        part: Part1,
+        fingerprints: &[Fingerprint::of(INPUTS[1])],
+        reason: "the answer is calculated at compile time for example 1",
+    }];
+    #[cfg(not(test))]
+    const INPUT_SPECIFIC: &'static [InputSpecific] = &[InputSpecific {
+        part: Part1,
+        fingerprints: &[Fingerprint::of(INPUTS[0])],
+        reason: "the answer is calculated at compile time for my input",
+    }];
     type Inputs = Inputs;
     type Parsed = Vec<(&'static str, char, Option<u8>)>;
     type Output = usize;
@@ -21,6 +34,10 @@ impl Day for Day15 {
         false
     }
 
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(Generator::input(rng, size))
+    }
+
     #[inline]
     fn parse(input: &'static str, part: Part) -> Result<Self::Parsed> {
         Ok(match part {
@@ -53,7 +70,8 @@ impl Day for Day15 {
                     boxx.retain(|(box_label, _)| box_label != label);
                 }
                 '=' => {
-                    let focal_length = focal_length.unwrap();
+                    let focal_length = focal_length
+                        .with_context(|| format!("Lens {label} has no focal length"))?;
                     if let Some((_, box_focal_length)) =
                         boxx.iter_mut().find(|(box_label, _)| box_label == label)
                     {
@@ -62,7 +80,7 @@ impl Day for Day15 {
                         boxx.push((*label, focal_length));
                     }
                 }
-                _ => unreachable!(),
+                _ => bail!("Unknown operation {operator:?} for lens {label}"),
             }
         }
 
@@ -93,6 +111,7 @@ const fn hash(value: &[u8]) -> usize {
     let mut sum = 0;
     while i < value.len() {
         match value[i] {
+            // Part 1 is calculated at compile time from the raw input, before it is normalized
             b'\r' | b'\n' => {}
             b',' => {
                 sum += current as usize;
@@ -110,7 +129,28 @@ impl Parser {
     fn input(s: &'static str) -> IResult<<Day15 as Day>::Parsed> {
         all_consuming(separated_list1(
             tag(","),
-            tuple((alpha1, alt((char('-'), char('='))), opt(u8))),
+            cut(context(
+                "a step like \"rn=1\" or \"cm-\"",
+                tuple((alpha1, alt((char('-'), char('='))), opt(u8))),
+            )),
         ))(s)
     }
 }
+
+struct Generator;
+impl Generator {
+    fn input(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                // A small set of labels, so lenses are replaced and removed as well
+                let label: String = (0..rng.usize(1..=3)).map(|_| rng.char('a'..='d')).collect();
+                if rng.u8(0..3) == 0 {
+                    format!("{label}-")
+                } else {
+                    format!("{label}={}", rng.u8(1..=9))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
@@ -3,9 +3,11 @@ use std::collections::HashSet;
 use nom::{
     branch::alt,
     character::complete::{char, line_ending},
-    combinator::{all_consuming, map},
-    multi::{many1, separated_list1},
+    combinator::{all_consuming, map, verify},
+    error::context,
+    multi::separated_list1,
 };
+use serde::Serialize;
 
 use super::inputs::{Inputs, INPUTS};
 use crate::prelude::*;
@@ -23,30 +25,76 @@ impl Day for Day16 {
         true
     }
 
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(Generator::input(rng, size))
+    }
+
     fn parse(input: &'static str, _part: Part) -> Result<Self::Parsed> {
         Ok(Parser::input(input)?.1)
     }
 
     fn part1(parsed: &Self::Parsed) -> Result<Self::Output> {
-        Ok(parsed.energized(Location {
-            x: 1,
-            y: 1,
-            direction: Right,
-        }))
+        Self::solve(parsed, Part1, &mut ())
     }
 
     fn part2(parsed: &Self::Parsed) -> Result<Self::Output> {
-        Ok(parsed
-            .borders()
-            .into_iter()
-            .map(|start| parsed.energized(start))
-            .max()
-            .unwrap())
+        Self::solve(parsed, Part2, &mut ())
+    }
+
+    fn observe(
+        parsed: &Self::Parsed,
+        part: Part,
+        observer: &mut dyn Observer,
+    ) -> Result<Option<Self::Output>> {
+        Self::solve(parsed, part, observer).map(Some)
+    }
+
+    fn visualize(parsed: &Self::Parsed, part: Part) -> Result<Option<Box<dyn Visualize + '_>>> {
+        let start = match part {
+            Part1 => Location::new(1, 1, Right),
+            Part2 => {
+                parsed
+                    .borders()
+                    .into_iter()
+                    .map(|start| Ok((parsed.energized(start, &mut ())?, start)))
+                    .collect::<Result<Vec<_>>>()?
+                    .into_iter()
+                    .max_by_key(|(energized, _)| *energized)
+                    .context("The contraption has no borders")?
+                    .1
+            }
+        };
+        Ok(Some(Box::new(Energized {
+            contraption: parsed,
+            tiles: parsed.energized_tiles(start, &mut ())?,
+        })))
     }
 }
 
+impl Day16 {
+    fn solve<O: Observer + ?Sized>(
+        parsed: &<Self as Day>::Parsed,
+        part: Part,
+        observer: &mut O,
+    ) -> Result<<Self as Day>::Output> {
+        match part {
+            Part1 => parsed.energized(Location::new(1, 1, Right), observer),
+            Part2 => parsed.borders().into_iter().try_fold(0, |max, start| {
+                Ok(max.max(parsed.energized(start, &mut *observer)?))
+            }),
+        }
+    }
+}
+
+#[derive(Serialize)]
 pub struct Contraption(Vec<Vec<char>>);
 
+/// The tiles a beam passes through, for `--visualize`.
+struct Energized<'a> {
+    contraption: &'a Contraption,
+    tiles: HashSet<(usize, usize)>,
+}
+
 #[derive(Clone, Copy, Hash, PartialEq, Eq)]
 struct Location {
     x: usize,
@@ -62,6 +110,17 @@ enum Direction {
     Right,
 }
 
+impl Direction {
+    fn arrow(self) -> char {
+        match self {
+            Up => '^',
+            Down => 'v',
+            Left => '<',
+            Right => '>',
+        }
+    }
+}
+
 impl Location {
     fn new(x: usize, y: usize, direction: Direction) -> Self {
         Self { x, y, direction }
@@ -98,7 +157,19 @@ impl Contraption {
         borders
     }
 
-    fn energized(&self, start: Location) -> <Day16 as Day>::Output {
+    fn energized<O: Observer + ?Sized>(
+        &self,
+        start: Location,
+        observer: &mut O,
+    ) -> Result<<Day16 as Day>::Output> {
+        Ok(self.energized_tiles(start, observer)?.len())
+    }
+
+    fn energized_tiles<O: Observer + ?Sized>(
+        &self,
+        start: Location,
+        observer: &mut O,
+    ) -> Result<HashSet<(usize, usize)>> {
         let mut locations = vec![start];
         let mut visited = HashSet::new();
         while let Some(mut location) = locations.pop() {
@@ -108,6 +179,11 @@ impl Contraption {
                 } else {
                     visited.insert(location);
                 }
+                observer.observe(&Event::Beam {
+                    x: location.x,
+                    y: location.y,
+                    direction: location.direction.arrow(),
+                });
 
                 let next = match c {
                     '-' => match location.direction {
@@ -137,18 +213,17 @@ impl Contraption {
                         Right => Up,
                     }),
                     '.' => location,
-                    _ => unreachable!(),
+                    _ => bail!("Unknown tile {c:?}"),
                 };
 
                 location = next.go();
             }
         }
 
-        visited
+        Ok(visited
             .into_iter()
             .map(|location| (location.x, location.y))
-            .collect::<HashSet<_>>()
-            .len()
+            .collect())
     }
 
     fn get(&self, x: usize, y: usize) -> Option<char> {
@@ -160,21 +235,57 @@ impl Contraption {
     }
 }
 
+impl Visualize for Energized<'_> {
+    fn visualize(&self) -> String {
+        let size = self.contraption.0.len();
+        grid(size, size, |x, y| {
+            let c = self.contraption.0[y][x];
+            match (self.tiles.contains(&(x + 1, y + 1)), c) {
+                (true, '.') => paint('#', Color::Yellow),
+                (true, _) => paint(c, Color::Red),
+                (false, _) => paint(c, Color::Gray),
+            }
+        })
+    }
+}
+
 struct Parser;
 impl Parser {
     fn input(s: &'static str) -> IResult<<Day16 as Day>::Parsed> {
         map(
-            all_consuming(separated_list1(
-                line_ending,
-                many1(alt((
-                    char('.'),
-                    char('-'),
-                    char('|'),
-                    char('\\'),
-                    char('/'),
-                ))),
+            all_consuming(context(
+                "a square contraption",
+                verify(
+                    separated_list1(
+                        line_ending,
+                        tiles(
+                            "a tile '.', '-', '|', '\\' or '/'",
+                            alt((char('.'), char('-'), char('|'), char('\\'), char('/'))),
+                        ),
+                    ),
+                    |rows: &Vec<Vec<char>>| rows.iter().all(|row| row.len() == rows.len()),
+                ),
             )),
             Contraption,
         )(s)
     }
 }
+
+struct Generator;
+impl Generator {
+    fn input(rng: &mut Rng, size: usize) -> String {
+        const TILES: [char; 4] = ['/', '\\', '|', '-'];
+
+        (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| match rng.u8(0..10) {
+                        0 => TILES[rng.usize(..TILES.len())],
+                        _ => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
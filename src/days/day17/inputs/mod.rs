@@ -1,6 +1,7 @@
-pub type Inputs = [&'static str; 3];
+pub type Inputs = [&'static str; 4];
 pub const INPUTS: Inputs = [
-    include_str!("input.txt"),
+    include_str!(concat!(env!("OUT_DIR"), "/day17/input.txt")),
     include_str!("example1.txt"),
     include_str!("example2.txt"),
+    include_str!("example3.txt"),
 ];
\ No newline at end of file
@@ -1,10 +1,11 @@
 use nom::{
     character::complete::{line_ending, one_of},
     combinator::{all_consuming, map},
-    multi::{many1, separated_list1},
+    multi::separated_list1,
 };
 
 use pathfinding::prelude::*;
+use serde::Serialize;
 
 use super::inputs::{Inputs, INPUTS};
 use crate::prelude::*;
@@ -22,6 +23,10 @@ impl Day for Day17 {
         true
     }
 
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(Generator::input(rng, size))
+    }
+
     fn parse(input: &'static str, _part: Part) -> Result<Self::Parsed> {
         Ok(Parser::input(input)?.1)
     }
@@ -33,11 +38,28 @@ impl Day for Day17 {
     fn part2(parsed: &Self::Parsed) -> Result<Self::Output> {
         parsed.least_heat_loss(4, 10)
     }
+
+    fn render(parsed: &Self::Parsed, part: Part) -> Result<Option<Box<dyn Render + '_>>> {
+        let (path, _) = match part {
+            Part1 => parsed.path(1, 3)?,
+            Part2 => parsed.path(4, 10)?,
+        };
+        Ok(Some(Box::new(Route {
+            heat_map: parsed,
+            path,
+        })))
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct HeatMap(Vec<Vec<usize>>);
 
+/// The path of least heat loss over the heat map, for `--render`.
+struct Route<'a> {
+    heat_map: &'a HeatMap,
+    path: Vec<Position>,
+}
+
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 struct Position {
     x: usize,
@@ -70,7 +92,11 @@ impl Opposite for Option<Direction> {
 
 impl HeatMap {
     fn least_heat_loss(&self, min: usize, max: usize) -> Result<<Day17 as Day>::Output> {
-        Ok(astar(
+        Ok(self.path(min, max)?.1)
+    }
+
+    fn path(&self, min: usize, max: usize) -> Result<(Vec<Position>, <Day17 as Day>::Output)> {
+        astar(
             &Position {
                 x: 1,
                 y: 1,
@@ -80,11 +106,16 @@ impl HeatMap {
             |position| self.heuristic(position),
             |position| self.success(position),
         )
-        .context("No path found")?
-        .1)
+        .context("No path found")
     }
 
     fn successors(&self, position: &Position, min: usize, max: usize) -> Vec<(Position, usize)> {
+        tracing::trace!(
+            x = position.x,
+            y = position.y,
+            direction = ?position.direction,
+            "expand"
+        );
         [Up, Down, Left, Right]
             .into_iter()
             .filter(|direction| {
@@ -147,17 +178,52 @@ impl HeatMap {
     }
 }
 
+impl Render for Route<'_> {
+    fn render(&self) -> Image {
+        let grid = &self.heat_map.0;
+        let mut image = Image::new(grid[0].len() as f64, grid.len() as f64);
+        for (y, row) in grid.iter().enumerate() {
+            for (x, heat_loss) in row.iter().enumerate() {
+                let t = (*heat_loss as f64 - 1.0) / 8.0;
+                image.tile(x, y, Rgb::blend(Rgb::YELLOW, Rgb::RED, t));
+            }
+        }
+        image.polyline(
+            self.path
+                .iter()
+                .map(|position| (position.x as f64 - 0.5, position.y as f64 - 0.5))
+                .collect(),
+            Rgb::BLUE,
+            0.3,
+        );
+        image
+    }
+}
+
 struct Parser;
 impl Parser {
     fn input(s: &'static str) -> IResult<<Day17 as Day>::Parsed> {
         map(
             all_consuming(separated_list1(
                 line_ending,
-                many1(map(one_of("123456789"), |c| {
-                    c.to_digit(10).unwrap() as usize
-                })),
+                tiles(
+                    "a heat loss from 1 to 9",
+                    map(one_of("123456789"), |c| c.to_digit(10).unwrap() as usize),
+                ),
             )),
             HeatMap,
         )(s)
     }
 }
+
+struct Generator;
+impl Generator {
+    /// A city of at least 5 by 5 blocks, so an ultra crucible can reach the factory.
+    fn input(rng: &mut Rng, size: usize) -> String {
+        let size = size + 4;
+        (0..size)
+            .map(|_| (0..size).map(|_| rng.char('1'..='9')).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
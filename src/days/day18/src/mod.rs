@@ -3,11 +3,11 @@ use std::collections::HashMap;
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_while_m_n},
-    character::complete::{anychar, char, i64, line_ending, not_line_ending},
+    character::complete::{anychar, char, i64, not_line_ending},
     combinator::{all_consuming, map, map_res},
-    multi::separated_list1,
     sequence::{delimited, preceded, tuple},
 };
+use serde::Serialize;
 
 use super::inputs::{Inputs, INPUTS};
 use crate::prelude::*;
@@ -25,6 +25,10 @@ impl Day for Day18 {
         false
     }
 
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(Generator::input(rng, size))
+    }
+
     fn parse(input: &'static str, part: Part) -> Result<Self::Parsed> {
         Ok(Parser::input(input, part)?.1)
     }
@@ -36,6 +40,10 @@ impl Day for Day18 {
     fn part2(parsed: &Self::Parsed) -> Result<Self::Output> {
         Self::cubic_meters(parsed)
     }
+
+    fn render(parsed: &Self::Parsed, _part: Part) -> Result<Option<Box<dyn Render + '_>>> {
+        Ok(Some(Box::new(Lagoon(parsed))))
+    }
 }
 
 impl Day18 {
@@ -147,13 +155,16 @@ impl Day18 {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+/// The lagoon dug out by following a dig plan, for `--render`.
+struct Lagoon<'a>(&'a <Day18 as Day>::Parsed);
+
+#[derive(Debug, Clone, Copy, Serialize)]
 pub struct PlanItem {
     direction: Direction,
     distance: isize,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize)]
 pub enum Direction {
     Left,
     Right,
@@ -249,11 +260,64 @@ impl Range {
     }
 }
 
+impl Render for Lagoon<'_> {
+    fn render(&self) -> Image {
+        let corners: Vec<_> = self
+            .0
+            .iter()
+            .scan(Position { x: 0, y: 0 }, |position, item| {
+                *position = position.next(item.direction, item.distance);
+                Some(*position)
+            })
+            .collect();
+
+        // Each cubic meter is a tile, so the trench runs through the middle of the tiles
+        let min_x = corners
+            .iter()
+            .map(|corner| corner.x)
+            .min()
+            .unwrap_or_default();
+        let max_x = corners
+            .iter()
+            .map(|corner| corner.x)
+            .max()
+            .unwrap_or_default();
+        let min_y = corners
+            .iter()
+            .map(|corner| corner.y)
+            .min()
+            .unwrap_or_default();
+        let max_y = corners
+            .iter()
+            .map(|corner| corner.y)
+            .max()
+            .unwrap_or_default();
+        let points: Vec<_> = corners
+            .iter()
+            .map(|corner| {
+                (
+                    (corner.x - min_x) as f64 + 0.5,
+                    (corner.y - min_y) as f64 + 0.5,
+                )
+            })
+            .collect();
+
+        let (width, height) = ((max_x - min_x + 1) as f64, (max_y - min_y + 1) as f64);
+        let mut image = Image::new(width, height);
+        image.polygon(points.clone(), Rgb::BLUE).polyline(
+            points.into_iter().cycle().take(corners.len() + 1).collect(),
+            Rgb::BLACK,
+            (width.max(height) / 200.0).max(1.0),
+        );
+        image
+    }
+}
+
 struct Parser;
 impl Parser {
     fn input(s: &str, part: Part) -> IResult<<Day18 as Day>::Parsed> {
-        all_consuming(separated_list1(
-            line_ending,
+        all_consuming(lines(
+            "a dig step like \"R 6 (#70c710)\"",
             match part {
                 Part1 => Parser::part1,
                 Part2 => Parser::part2,
@@ -316,3 +380,118 @@ impl Parser {
         )(s)
     }
 }
+
+struct Generator;
+impl Generator {
+    /// A dig plan around columns of random heights, so the trench never crosses itself.
+    fn input(rng: &mut Rng, size: usize) -> String {
+        let size = size as isize;
+        let mut x = 0;
+        let mut columns = Vec::new();
+        let (mut bottom, mut top) = (0, rng.isize(1..=size));
+        for _ in 0..rng.usize(1..=size as usize) {
+            columns.push((x, bottom, top));
+            x += rng.isize(1..=size);
+            // The next column overlaps this one
+            let next_bottom = rng.isize(bottom - size..top);
+            top = rng.isize(next_bottom.max(bottom) + 1..=next_bottom.max(bottom) + size);
+            bottom = next_bottom;
+        }
+
+        let (first_x, first_bottom, _) = columns[0];
+        let mut corners = vec![(first_x, first_bottom)];
+        for (i, &(x, _, top)) in columns.iter().enumerate() {
+            let end = columns
+                .get(i + 1)
+                .map_or(x + rng.isize(1..=size), |column| column.0);
+            corners.extend([(x, top), (end, top)]);
+        }
+        let end = corners.last().unwrap().0;
+        for (i, &(x, bottom, _)) in columns.iter().enumerate().rev() {
+            let end = columns.get(i + 1).map_or(end, |column| column.0);
+            corners.extend([(end, bottom), (x, bottom)]);
+        }
+
+        let mut plan: Vec<(char, isize)> = Vec::new();
+        for corners in corners.windows(2) {
+            let ((x1, y1), (x2, y2)) = (corners[0], corners[1]);
+            let (direction, distance) = match (x2 - x1, y2 - y1) {
+                (0, 0) => continue,
+                (0, dy) if dy > 0 => ('D', dy),
+                (0, dy) => ('U', -dy),
+                (dx, _) if dx > 0 => ('R', dx),
+                (dx, _) => ('L', -dx),
+            };
+            match plan.last_mut() {
+                Some(last) if last.0 == direction => last.1 += distance,
+                _ => plan.push((direction, distance)),
+            }
+        }
+
+        plan.into_iter()
+            .map(|(direction, distance)| {
+                let hex = match direction {
+                    'R' => 0,
+                    'D' => 1,
+                    'L' => 2,
+                    _ => 3,
+                };
+                format!("{direction} {distance} (#{distance:05x}{hex})")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn cubic_meters_agrees_with_flood_fill() {
+    use std::collections::HashSet;
+
+    crate::property::check(6, Generator::input, |input| {
+        let input = crate::property::leak(input.clone());
+        let plan = Day18::parse(input, Part1)?;
+        ensure!(Day18::parse(input, Part2)?.len() == plan.len());
+
+        let mut position = Position { x: 0, y: 0 };
+        let mut trench = HashSet::from([position]);
+        for item in &plan {
+            for _ in 0..item.distance {
+                position = position.next(item.direction, 1);
+                trench.insert(position);
+            }
+        }
+        ensure!(
+            position == Position { x: 0, y: 0 },
+            "The trench is not a loop"
+        );
+
+        // Flood fill the ground outside of the trench, within a border around it
+        let min_x = trench.iter().map(|p| p.x).min().unwrap() - 1;
+        let max_x = trench.iter().map(|p| p.x).max().unwrap() + 1;
+        let min_y = trench.iter().map(|p| p.y).min().unwrap() - 1;
+        let max_y = trench.iter().map(|p| p.y).max().unwrap() + 1;
+        let mut outside = HashSet::from([Position { x: min_x, y: min_y }]);
+        let mut queue = vec![Position { x: min_x, y: min_y }];
+        while let Some(position) = queue.pop() {
+            for direction in [Up, Down, Left, Right] {
+                let next = position.next(direction, 1);
+                if (min_x..=max_x).contains(&next.x)
+                    && (min_y..=max_y).contains(&next.y)
+                    && !trench.contains(&next)
+                    && outside.insert(next)
+                {
+                    queue.push(next);
+                }
+            }
+        }
+        let expected = (max_x - min_x + 1) * (max_y - min_y + 1) - outside.len() as isize;
+
+        let actual = Day18::cubic_meters(&plan)?;
+        ensure!(
+            actual == expected,
+            "Sweep gives {actual}, expected {expected}"
+        );
+        Ok(())
+    });
+}
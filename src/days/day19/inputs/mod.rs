@@ -1,5 +1,6 @@
-pub type Inputs = [&'static str; 2];
+pub type Inputs = [&'static str; 3];
 pub const INPUTS: Inputs = [
-    include_str!("input.txt"),
+    include_str!(concat!(env!("OUT_DIR"), "/day19/input.txt")),
     include_str!("example1.txt"),
+    include_str!("example2.txt"),
 ];
\ No newline at end of file
@@ -7,10 +7,12 @@ use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{alpha1, char, line_ending, u64},
-    combinator::{all_consuming, map},
+    combinator::{all_consuming, cut, map},
+    error::context,
     multi::{many_m_n, separated_list1},
     sequence::{delimited, preceded, separated_pair, tuple},
 };
+use serde::Serialize;
 
 use super::inputs::{Inputs, INPUTS};
 use crate::prelude::*;
@@ -26,6 +28,10 @@ impl Day for Day19 {
         true
     }
 
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(Generator::input(rng, size))
+    }
+
     fn parse(input: &'static str, _part: Part) -> Result<Self::Parsed> {
         Ok(Parser::input(input)?.1)
     }
@@ -41,7 +47,7 @@ impl Day for Day19 {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize)]
 pub enum Category {
     ExtremelyCoolLooking,
     Musical,
@@ -51,17 +57,21 @@ pub enum Category {
 
 pub type Value = usize;
 
+#[derive(Serialize)]
 pub struct System(HashMap<Label, Workflow>);
 
 pub type Label = &'static str;
 
+#[derive(Serialize)]
 pub struct Workflow(Vec<Rule>);
 
+#[derive(Serialize)]
 pub enum Rule {
     Condition(Condition),
     Destination(Destination),
 }
 
+#[derive(Serialize)]
 pub struct Condition {
     category: Category,
     operator: Operator,
@@ -69,24 +79,25 @@ pub struct Condition {
     destination: Destination,
 }
 
+#[derive(Serialize)]
 pub enum Operator {
     LessThan,
     GreaterThan,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize)]
 pub enum Destination {
     Decision(Decision),
     Workflow(Label),
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum Decision {
     Accepted,
     Rejected,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Serialize)]
 pub struct PartRating {
     x: Value,
     m: Value,
@@ -290,22 +301,23 @@ impl Range {
             .0
             .iter()
             .copied()
-            .fold(Vec::new(), |mut sub_ranges, mut sub_range| {
-                if sub_range.contains(start) {
-                    possibilities += end.min(sub_range.end) - start + 1;
-                    if sub_range.contains(end) && end < sub_range.end {
-                        sub_ranges.push(SubRange {
-                            start: end + 1,
-                            end: sub_range.end,
-                        });
-                    }
-                    sub_range.end = start - 1;
-                } else if sub_range.contains(end) {
-                    possibilities += end - sub_range.start + 1;
-                    sub_range.start = end + 1;
-                }
-                if sub_range.start <= sub_range.end {
+            .fold(Vec::new(), |mut sub_ranges, sub_range| {
+                if sub_range.end < start || sub_range.start > end {
                     sub_ranges.push(sub_range);
+                    return sub_ranges;
+                }
+                possibilities += end.min(sub_range.end) - start.max(sub_range.start) + 1;
+                if sub_range.start < start {
+                    sub_ranges.push(SubRange {
+                        start: sub_range.start,
+                        end: start - 1,
+                    });
+                }
+                if sub_range.end > end {
+                    sub_ranges.push(SubRange {
+                        start: end + 1,
+                        end: sub_range.end,
+                    });
                 }
                 sub_ranges
             });
@@ -318,10 +330,6 @@ impl Range {
 }
 
 impl SubRange {
-    fn contains(&self, i: usize) -> bool {
-        self.start <= i && self.end >= i
-    }
-
     fn possibilities_accepted(&self) -> Value {
         self.end - self.start + 1
     }
@@ -374,14 +382,21 @@ impl Parser {
         all_consuming(separated_pair(
             Parser::system,
             many_m_n(2, 2, line_ending),
-            separated_list1(line_ending, Parser::part_rating),
+            lines(
+                "a part rating like \"{x=787,m=2655,a=1222,s=2876}\"",
+                Parser::part_rating,
+            ),
         ))(s)
     }
 
     fn system(s: &'static str) -> IResult<System> {
-        map(separated_list1(line_ending, Parser::workflow), |vec| {
-            System(vec.into_iter().collect())
-        })(s)
+        map(
+            separated_list1(
+                line_ending,
+                context("a workflow like \"px{a<2006:qkq,rfg}\"", Parser::workflow),
+            ),
+            |vec| System(vec.into_iter().collect()),
+        )(s)
     }
 
     fn workflow(s: &'static str) -> IResult<(Label, Workflow)> {
@@ -406,8 +421,12 @@ impl Parser {
             tuple((
                 Parser::category,
                 Parser::operator,
-                Parser::value,
-                preceded(tag(":"), Parser::destination),
+                // After category and operator this can only be a condition, so don't backtrack
+                cut(context("a value", Parser::value)),
+                cut(context(
+                    "':' and a destination",
+                    preceded(tag(":"), Parser::destination),
+                )),
             )),
             |(category, operator, value, destination)| Condition {
                 category,
@@ -466,3 +485,100 @@ impl Parser {
         )(s)
     }
 }
+
+struct Generator;
+impl Generator {
+    fn input(rng: &mut Rng, size: usize) -> String {
+        let workflows = rng.usize(1..=size.min(8));
+        let label = |workflow: usize| match workflow {
+            0 => "in".to_string(),
+            _ => format!("w{}", (b'a' + workflow as u8) as char),
+        };
+        // Only refer to later workflows, so there are no cycles
+        let destination =
+            |rng: &mut Rng, workflow: usize| match rng.usize(workflow + 1..workflows + 2) {
+                next if next < workflows => label(next),
+                next if next == workflows => "A".to_string(),
+                _ => "R".to_string(),
+            };
+
+        let system = (0..workflows)
+            .map(|workflow| {
+                let mut rules = (0..rng.usize(0..=3))
+                    .map(|_| {
+                        format!(
+                            "{}{}{}:{}",
+                            rng.choice(['x', 'm', 'a', 's']).unwrap(),
+                            rng.choice(['<', '>']).unwrap(),
+                            rng.usize(1..=4000),
+                            destination(rng, workflow)
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                rules.push(destination(rng, workflow));
+                format!("{}{{{}}}", label(workflow), rules.join(","))
+            })
+            .collect::<Vec<_>>();
+
+        let part_ratings = (0..rng.usize(1..=3))
+            .map(|_| {
+                let [x, m, a, s] = [(); 4].map(|_| rng.usize(1..=4000));
+                format!("{{x={x},m={m},a={a},s={s}}}")
+            })
+            .collect::<Vec<_>>();
+
+        format!("{}\n\n{}", system.join("\n"), part_ratings.join("\n"))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn range_splitting_agrees_with_process() {
+    crate::property::check(8, Generator::input, |input| {
+        let parsed = Day19::parse(crate::property::leak(input.clone()), Part2)?;
+        let (system, _) = &parsed;
+
+        // The decision only changes at the values in the conditions, so it is enough to process
+        // one part rating per combination of intervals between those values.
+        let mut starts = [(); 4].map(|_| vec![1, 4001]);
+        for workflow in system.0.values() {
+            for rule in &workflow.0 {
+                if let Rule::Condition(condition) = rule {
+                    let start = match condition.operator {
+                        Operator::LessThan => condition.value,
+                        Operator::GreaterThan => condition.value + 1,
+                    };
+                    starts[condition.category as usize].push(start.clamp(1, 4001));
+                }
+            }
+        }
+        let intervals = starts.map(|mut starts| {
+            starts.sort();
+            starts.dedup();
+            starts
+                .windows(2)
+                .map(|starts| (starts[0], starts[1] - starts[0]))
+                .collect::<Vec<_>>()
+        });
+
+        let mut expected = 0;
+        for &(x, x_count) in &intervals[0] {
+            for &(m, m_count) in &intervals[1] {
+                for &(a, a_count) in &intervals[2] {
+                    for &(s, s_count) in &intervals[3] {
+                        if system.process(PartRating { x, m, a, s })? == Decision::Accepted {
+                            expected += x_count * m_count * a_count * s_count;
+                        }
+                    }
+                }
+            }
+        }
+
+        let actual = Day19::part2(&parsed)?;
+        ensure!(
+            actual == expected,
+            "Range splitting gives {actual}, expected {expected}"
+        );
+        Ok(())
+    });
+}
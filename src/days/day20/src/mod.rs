@@ -3,13 +3,14 @@ use std::collections::{HashMap, VecDeque};
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{alpha1, line_ending},
+    character::complete::alpha1,
     combinator::{all_consuming, map},
     multi::separated_list1,
     sequence::{preceded, separated_pair},
 };
 use num_integer::Integer;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use serde::Serialize;
 
 use super::inputs::{Inputs, INPUTS};
 use crate::prelude::*;
@@ -25,42 +26,135 @@ impl Day for Day20 {
         true
     }
 
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(Generator::input(rng, size))
+    }
+
     fn parse(input: &'static str, _part: Part) -> Result<Self::Parsed> {
         Ok(Parser::input(input)?.1)
     }
 
     fn part1(parsed: &Self::Parsed) -> Result<Self::Output> {
+        Ok(Self::pulses(parsed, &mut ()))
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Output> {
+        let system = System::from(parsed);
+        let cycles = system
+            .find_rx_grandparents()?
+            .par_iter()
+            .map(|rx_grandparent| Self::presses(&system, rx_grandparent, &mut ()))
+            .collect();
+        Self::combine(cycles)
+    }
+
+    fn observe(
+        parsed: &Self::Parsed,
+        part: Part,
+        observer: &mut dyn Observer,
+    ) -> Result<Option<Self::Output>> {
+        match part {
+            Part1 => Ok(Some(Self::pulses(parsed, observer))),
+            Part2 => {
+                // One grandparent after the other, as the observer can't be shared between threads
+                let system = System::from(parsed);
+                let cycles = system
+                    .find_rx_grandparents()?
+                    .iter()
+                    .map(|rx_grandparent| Self::presses(&system, rx_grandparent, &mut *observer))
+                    .collect();
+                Self::combine(cycles).map(Some)
+            }
+        }
+    }
+
+    fn validate(parsed: &Self::Parsed) -> Vec<Violation> {
+        let assumption = "rx is fed by a single conjunction, which is fed by conjunctions only";
+        let is_conjunction = |name: ModuleName| {
+            parsed.iter().any(|((module_type, module_name), _)| {
+                *module_name == name && matches!(module_type, ModuleType::Conjunction)
+            })
+        };
+        let parents = |name: ModuleName| {
+            parsed
+                .iter()
+                .filter(move |(_, destinations)| destinations.contains(&name))
+                .map(|((_, module_name), _)| *module_name)
+        };
+
+        let rx_parents = parents("rx").collect::<Vec<_>>();
+        let [rx_parent] = rx_parents[..] else {
+            return vec![Violation::new(
+                Part2,
+                assumption,
+                format!("rx has {} parents instead of 1", rx_parents.len()),
+            )];
+        };
+
+        if !is_conjunction(rx_parent) {
+            return vec![Violation::new(
+                Part2,
+                assumption,
+                format!("Parent {rx_parent} of rx is not a conjunction"),
+            )];
+        }
+
+        let rx_grandparents = parents(rx_parent).collect::<Vec<_>>();
+        if rx_grandparents.is_empty() {
+            return vec![Violation::new(
+                Part2,
+                assumption,
+                format!("Parent {rx_parent} of rx has no parents"),
+            )];
+        }
+
+        rx_grandparents
+            .into_iter()
+            .filter(|grandparent| !is_conjunction(grandparent))
+            .map(|grandparent| {
+                Violation::new(
+                    Part2,
+                    assumption,
+                    format!("Grandparent {grandparent} of rx is not a conjunction"),
+                )
+            })
+            .collect()
+    }
+}
+
+impl Day20 {
+    /// The product of the low and high pulses sent by pressing the button 1000 times.
+    fn pulses<O: Observer + ?Sized>(parsed: &<Self as Day>::Parsed, observer: &mut O) -> usize {
         let mut system = System::from(parsed);
         let mut counter = Part1Counter::default();
 
         for _ in 1..=1000 {
-            system.push_button(&mut counter);
+            system.push_button(&mut (&mut counter, &mut *observer));
         }
 
-        Ok(counter.product())
+        counter.product()
     }
 
-    fn part2(parsed: &Self::Parsed) -> Result<Self::Output> {
-        let system = System::from(parsed);
-        let rx_grandparents = system.find_rx_grandparents()?;
-
-        let cycles = rx_grandparents
-            .par_iter()
-            .map(|rx_grandparent| {
-                let mut system = system.clone();
-                let mut counter = Part2Counter::new(rx_grandparent);
-
-                for count in 1usize.. {
-                    system.push_button(&mut counter);
-                    if counter.reached() {
-                        return count;
-                    }
-                }
+    /// The number of button presses until `destination` receives a low pulse.
+    fn presses<O: Observer + ?Sized>(
+        system: &System,
+        destination: ModuleName,
+        observer: &mut O,
+    ) -> usize {
+        let mut system = system.clone();
+        let mut counter = Part2Counter::new(destination);
+
+        for count in 1usize.. {
+            system.push_button(&mut (&mut counter, &mut *observer));
+            if counter.reached() {
+                return count;
+            }
+        }
 
-                unreachable!()
-            })
-            .collect::<Vec<_>>();
+        unreachable!()
+    }
 
+    fn combine(cycles: Vec<usize>) -> Result<<Self as Day>::Output> {
         cycles
             .into_iter()
             .reduce(|a, b| a.lcm(&b))
@@ -70,6 +164,7 @@ impl Day for Day20 {
 
 pub type ModuleName = &'static str;
 
+#[derive(Serialize)]
 pub enum ModuleType {
     Broadcaster,
     FlipFlop,
@@ -101,10 +196,6 @@ struct Signal {
     pulse: Pulse,
 }
 
-trait Counter {
-    fn add(&mut self, system: &System, signal: Signal);
-}
-
 #[derive(Default)]
 struct Part1Counter {
     low: usize,
@@ -142,7 +233,7 @@ impl<'a> From<&'a <Day20 as Day>::Parsed> for System<'a> {
 }
 
 impl<'a> System<'a> {
-    fn push_button(&mut self, counter: &mut impl Counter) {
+    fn push_button(&mut self, observer: &mut impl Observer) {
         let mut dequeue = VecDeque::from([Signal {
             source: "button",
             destination: "broadcaster",
@@ -150,7 +241,17 @@ impl<'a> System<'a> {
         }]);
 
         while let Some(signal) = dequeue.pop_front() {
-            counter.add(self, signal);
+            tracing::trace!(
+                source = signal.source,
+                destination = signal.destination,
+                pulse = if signal.pulse == HIGH { "high" } else { "low" },
+                "pulse"
+            );
+            observer.observe(&Event::Pulse {
+                source: signal.source,
+                destination: signal.destination,
+                high: signal.pulse == HIGH,
+            });
             self.process_signal(signal, &mut dequeue);
         }
     }
@@ -226,11 +327,12 @@ impl Module {
     }
 }
 
-impl Counter for Part1Counter {
-    fn add(&mut self, _system: &System, signal: Signal) {
-        match signal.pulse {
-            LOW => self.low += 1,
-            HIGH => self.high += 1,
+impl Observer for Part1Counter {
+    fn observe(&mut self, event: &Event) {
+        match event {
+            Event::Pulse { high: false, .. } => self.low += 1,
+            Event::Pulse { high: true, .. } => self.high += 1,
+            _ => {}
         }
     }
 }
@@ -241,10 +343,17 @@ impl Part1Counter {
     }
 }
 
-impl Counter for Part2Counter {
-    fn add(&mut self, _system: &System, signal: Signal) {
-        if signal.destination == self.destination && signal.pulse == LOW {
-            self.reached = true;
+impl Observer for Part2Counter {
+    fn observe(&mut self, event: &Event) {
+        if let Event::Pulse {
+            destination,
+            high: false,
+            ..
+        } = event
+        {
+            if *destination == self.destination {
+                self.reached = true;
+            }
         }
     }
 }
@@ -265,8 +374,8 @@ impl Part2Counter {
 struct Parser;
 impl Parser {
     fn input(s: &'static str) -> IResult<<Day20 as Day>::Parsed> {
-        all_consuming(separated_list1(
-            line_ending,
+        all_consuming(lines(
+            "a module like \"%a -> inv, con\"",
             separated_pair(
                 Parser::sender,
                 tag(" -> "),
@@ -292,6 +401,66 @@ impl Parser {
     }
 }
 
+struct Generator;
+impl Generator {
+    /// Four binary counters like in the real input. Each counter is a chain of flip-flops, of
+    /// which the ones of its set bits feed a conjunction. The conjunction resets the counter when
+    /// it reaches its number, which is at most `size`.
+    fn input(rng: &mut Rng, size: usize) -> String {
+        let mut names = (0..).map(|id: usize| {
+            let mut name = String::from("m");
+            let mut id = id;
+            loop {
+                name.push((b'a' + (id % 26) as u8) as char);
+                id /= 26;
+                if id == 0 {
+                    break name;
+                }
+            }
+        });
+        let mut names = move || names.next().unwrap();
+
+        let final_conjunction = names();
+        let mut lines = Vec::new();
+        let mut starts = Vec::new();
+
+        for _ in 0..4 {
+            let number = rng.usize(size / 2..=size) | 1;
+            let bits = usize::BITS - number.leading_zeros();
+            let flip_flops: Vec<_> = (0..bits).map(|_| names()).collect();
+            let conjunction = names();
+            let inverter = names();
+
+            for (bit, flip_flop) in flip_flops.iter().enumerate() {
+                let destinations: Vec<_> = flip_flops
+                    .get(bit + 1)
+                    .into_iter()
+                    .chain((number >> bit & 1 == 1).then_some(&conjunction))
+                    .cloned()
+                    .collect();
+                lines.push(format!("%{flip_flop} -> {}", destinations.join(", ")));
+            }
+
+            let resets: Vec<_> = flip_flops
+                .iter()
+                .enumerate()
+                .filter(|(bit, _)| *bit == 0 || number >> bit & 1 == 0)
+                .map(|(_, flip_flop)| flip_flop.clone())
+                .chain([inverter.clone()])
+                .collect();
+            lines.push(format!("&{conjunction} -> {}", resets.join(", ")));
+            lines.push(format!("&{inverter} -> {final_conjunction}"));
+
+            starts.push(flip_flops[0].clone());
+        }
+
+        lines.push(format!("&{final_conjunction} -> rx"));
+        lines.push(format!("broadcaster -> {}", starts.join(", ")));
+        rng.shuffle(&mut lines);
+        lines.join("\n")
+    }
+}
+
 #[cfg(test)]
 #[test]
 fn print_graphviz() -> Result<()> {
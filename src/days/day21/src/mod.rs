@@ -1,10 +1,10 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use nom::{
     branch::alt,
     character::complete::{char, line_ending},
     combinator::all_consuming,
-    multi::{many1, separated_list1},
+    multi::separated_list1,
 };
 
 use super::inputs::{Inputs, INPUTS};
@@ -13,6 +13,23 @@ use crate::prelude::*;
 pub struct Day21;
 impl Day for Day21 {
     const INPUTS: Self::Inputs = INPUTS;
+    const INPUT_SPECIFIC: &'static [InputSpecific] = &[InputSpecific {
+        part: Part2,
+        fingerprints: &[Fingerprint::of(INPUTS[0])],
+        reason: "the answer is hard-coded for my input",
+    }];
+    const VARIANTS: &'static [Variant<Self::Parsed, Self::Output>] = &[
+        Variant {
+            name: "distances",
+            part: Part1,
+            solve: |parsed| Ok(Self::garden_plots_by_distance(parsed, 64)),
+        },
+        Variant {
+            name: "distances",
+            part: Part2,
+            solve: |parsed| Ok(Self::infinite_garden_plots(parsed, 26501365)),
+        },
+    ];
     type Inputs = Inputs;
     type Parsed = Vec<Vec<char>>;
     type Output = usize;
@@ -21,21 +38,120 @@ impl Day for Day21 {
         true
     }
 
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(Generator::input(rng, size))
+    }
+
     fn parse(input: &'static str, _part: Part) -> Result<Self::Parsed> {
         Ok(Parser::input(input)?.1)
     }
 
     fn part1(parsed: &Self::Parsed) -> Result<Self::Output> {
-        let rocks = parsed
+        Ok(Self::garden_plots(parsed, 64))
+    }
+
+    fn part2(_parsed: &Self::Parsed) -> Result<Self::Output> {
+        // This one was too difficult. After reading how to solve it on reddit, I didn't want to implement it on my own,
+        // so I took the following solution:
+        // https://gist.githubusercontent.com/icub3d/70d8aced2636ee631b66cdb590185df7/raw/a204099f57814f7918f9799aeb04137928c0b05b/main.rs
+        // It is ported as variant "distances" (see `infinite_garden_plots`). Thanks /u/icub3d!
+        Ok(618261433219147)
+    }
+
+    fn visualize(parsed: &Self::Parsed, part: Part) -> Result<Option<Box<dyn Visualize + '_>>> {
+        Ok(match part {
+            Part1 => Some(Box::new(Garden {
+                map: parsed,
+                reached: Self::reached_plots(parsed, 64, &mut ()),
+            })),
+            Part2 => None,
+        })
+    }
+
+    fn observe(
+        parsed: &Self::Parsed,
+        part: Part,
+        observer: &mut dyn Observer,
+    ) -> Result<Option<Self::Output>> {
+        Ok(match part {
+            Part1 => Some(Self::reached_plots(parsed, 64, observer).len()),
+            Part2 => None,
+        })
+    }
+
+    fn validate(parsed: &Self::Parsed) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let size = parsed.len();
+
+        if let Some(row) = parsed.iter().find(|row| row.len() != size) {
+            violations.push(Violation::new(
+                Part2,
+                "The garden is a square",
+                format!(
+                    "The garden has {size} rows, but a row of {} tiles",
+                    row.len()
+                ),
+            ));
+            return violations;
+        }
+
+        let start = parsed
             .iter()
             .enumerate()
-            .flat_map(|(y, line)| {
-                line.iter()
-                    .enumerate()
-                    .filter(|(_, c)| **c == '#')
-                    .map(move |(x, _)| (x, y))
-            })
-            .collect::<HashSet<_>>();
+            .find_map(|(y, row)| row.iter().position(|c| *c == 'S').map(|x| (x, y)));
+        let Some((x, y)) = start else {
+            violations.push(Violation::new(
+                Part2,
+                "The start is in the center",
+                "There is no start",
+            ));
+            return violations;
+        };
+
+        if size % 2 == 0 || x != size / 2 || y != size / 2 {
+            violations.push(Violation::new(
+                Part2,
+                "The start is in the center",
+                format!("The start is at ({x}, {y}) of a {size}x{size} garden"),
+            ));
+        }
+
+        if parsed[y].contains(&'#') {
+            violations.push(Violation::new(
+                Part2,
+                "The row of the start is clear",
+                format!("Row {y} contains rocks"),
+            ));
+        }
+
+        if parsed.iter().any(|row| row[x] == '#') {
+            violations.push(Violation::new(
+                Part2,
+                "The column of the start is clear",
+                format!("Column {x} contains rocks"),
+            ));
+        }
+
+        violations
+    }
+}
+
+impl Day21 {
+    fn garden_plots(parsed: &<Self as Day>::Parsed, steps: usize) -> usize {
+        Self::reached_plots(parsed, steps, &mut ()).len()
+    }
+
+    fn reached_plots<O: Observer + ?Sized>(
+        parsed: &<Self as Day>::Parsed,
+        steps: usize,
+        observer: &mut O,
+    ) -> HashSet<(usize, usize)> {
+        let is_garden_plot = |&(x, y): &(usize, usize)| {
+            parsed
+                .get(y)
+                .and_then(|line| line.get(x))
+                .is_some_and(|c| *c != '#')
+        };
 
         let mut garden_plots = parsed
             .iter()
@@ -48,25 +164,120 @@ impl Day for Day21 {
             })
             .collect::<HashSet<_>>();
 
-        for _ in 1..=64 {
+        for step in 1..=steps {
             garden_plots = garden_plots
                 .into_iter()
-                .flat_map(|(x, y)| [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)])
-                .collect::<HashSet<_>>()
-                .difference(&rocks)
-                .copied()
+                .flat_map(|(x, y)| {
+                    [
+                        (x.wrapping_sub(1), y),
+                        (x + 1, y),
+                        (x, y.wrapping_sub(1)),
+                        (x, y + 1),
+                    ]
+                })
+                .filter(is_garden_plot)
                 .collect();
+            observer.observe(&Event::Layer {
+                step,
+                plots: garden_plots.len(),
+            });
         }
 
-        Ok(garden_plots.len())
+        garden_plots
     }
 
-    fn part2(_parsed: &Self::Parsed) -> Result<Self::Output> {
-        // This one was too difficult. After reading how to solve it on reddit, I didn't want to implement it on my own,
-        // so I took the following solution:
-        // https://gist.githubusercontent.com/icub3d/70d8aced2636ee631b66cdb590185df7/raw/a204099f57814f7918f9799aeb04137928c0b05b/main.rs
-        // This file is copied to main.rs in this directory. Thanks /u/icub3d!
-        Ok(618261433219147)
+    /// The fewest number of steps from the start to every reachable garden plot.
+    fn distances(parsed: &<Self as Day>::Parsed) -> HashMap<(usize, usize), usize> {
+        let mut distances = HashMap::new();
+        let mut frontier = parsed
+            .iter()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.iter()
+                    .enumerate()
+                    .filter(|(_, c)| **c == 'S')
+                    .map(move |(x, _)| ((x, y), 0))
+            })
+            .collect::<VecDeque<_>>();
+
+        while let Some(((x, y), distance)) = frontier.pop_front() {
+            if distances.contains_key(&(x, y)) {
+                continue;
+            }
+            distances.insert((x, y), distance);
+
+            for (x, y) in [
+                (x.wrapping_sub(1), y),
+                (x + 1, y),
+                (x, y.wrapping_sub(1)),
+                (x, y + 1),
+            ] {
+                if parsed
+                    .get(y)
+                    .and_then(|line| line.get(x))
+                    .is_some_and(|c| *c != '#')
+                {
+                    frontier.push_back(((x, y), distance + 1));
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// A garden plot is reached in exactly `steps` steps if it can be reached in fewer steps with
+    /// the same parity, by stepping back and forth.
+    fn garden_plots_by_distance(parsed: &<Self as Day>::Parsed, steps: usize) -> usize {
+        Self::distances(parsed)
+            .into_values()
+            .filter(|distance| *distance <= steps && distance % 2 == steps % 2)
+            .count()
+    }
+
+    /// The garden plots reached on the infinitely repeated garden, by /u/icub3d. Relies on the
+    /// assumptions checked by `validate`: the walk ends at the edge of a garden, so the reached
+    /// plots form a diamond of whole gardens of alternating parity, minus or plus their corners.
+    fn infinite_garden_plots(parsed: &<Self as Day>::Parsed, steps: usize) -> usize {
+        let size = parsed.len();
+        let half = size / 2;
+        let count = (steps - half) / size;
+
+        let (mut odd, mut even, mut odd_corners, mut even_corners) = (0, 0, 0, 0);
+        for distance in Self::distances(parsed).into_values() {
+            match (distance % 2 == 1, distance > half) {
+                (true, corner) => {
+                    odd += 1;
+                    odd_corners += corner as usize;
+                }
+                (false, corner) => {
+                    even += 1;
+                    even_corners += corner as usize;
+                }
+            }
+        }
+
+        odd * (count + 1) * (count + 1) + even * count * count - (count + 1) * odd_corners
+            + count * even_corners
+    }
+}
+
+/// The garden plots reached in exactly the number of steps, for `--visualize`.
+struct Garden<'a> {
+    map: &'a <Day21 as Day>::Parsed,
+    reached: HashSet<(usize, usize)>,
+}
+
+impl Visualize for Garden<'_> {
+    fn visualize(&self) -> String {
+        let width = self.map.first().map_or(0, Vec::len);
+        grid(width, self.map.len(), |x, y| {
+            match self.map[y].get(x).copied().unwrap_or(' ') {
+                'S' => paint('S', Color::Red),
+                _ if self.reached.contains(&(x, y)) => paint('O', Color::Green),
+                '#' => paint('#', Color::Gray),
+                c => c.to_string(),
+            }
+        })
     }
 }
 
@@ -75,7 +286,79 @@ impl Parser {
     fn input(s: &'static str) -> IResult<<Day21 as Day>::Parsed> {
         all_consuming(separated_list1(
             line_ending,
-            many1(alt((char('S'), char('.'), char('#')))),
+            tiles(
+                "a tile 'S', '.' or '#'",
+                alt((char('S'), char('.'), char('#'))),
+            ),
         ))(s)
     }
 }
+
+struct Generator;
+impl Generator {
+    fn input(rng: &mut Rng, size: usize) -> String {
+        let size = 2 * size + 1;
+        (0..size)
+            .map(|y| {
+                (0..size)
+                    .map(|x| match (x, y) {
+                        _ if x == size / 2 && y == size / 2 => 'S',
+                        // Keep the middle row and column clear, like in the real input
+                        _ if x == size / 2 || y == size / 2 => '.',
+                        _ if rng.u8(0..10) < 3 => '#',
+                        _ => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn garden_plots_agrees_with_naive() {
+    // The naive approach of /u/icub3d: follow every path of exactly the number of steps
+    fn naive(
+        garden: &[Vec<char>],
+        steps: usize,
+        (x, y): (usize, usize),
+        plots: &mut HashSet<(usize, usize)>,
+    ) {
+        if steps == 0 {
+            plots.insert((x, y));
+            return;
+        }
+        for (x, y) in [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ] {
+            if garden
+                .get(y)
+                .and_then(|row| row.get(x))
+                .is_some_and(|c| *c != '#')
+            {
+                naive(garden, steps - 1, (x, y), plots);
+            }
+        }
+    }
+
+    crate::property::check(7, Generator::input, |input| {
+        let garden = Day21::parse(crate::property::leak(input.clone()), Part1)?;
+        let center = garden.len() / 2;
+        // The garden is big enough to never walk off it
+        for steps in 0..=center {
+            let mut plots = HashSet::new();
+            naive(&garden, steps, (center, center), &mut plots);
+            let actual = Day21::garden_plots(&garden, steps);
+            ensure!(
+                actual == plots.len(),
+                "{actual} garden plots after {steps} steps, expected {}",
+                plots.len()
+            );
+        }
+        Ok(())
+    });
+}
@@ -1,10 +1,11 @@
 use nom::{
     bytes::complete::tag,
-    character::complete::{line_ending, u64},
-    combinator::{all_consuming, map},
-    multi::separated_list1,
+    character::complete::u64,
+    combinator::{all_consuming, map, verify},
+    error::context,
     sequence::{preceded, separated_pair, tuple},
 };
+use serde::Serialize;
 
 use super::inputs::{Inputs, INPUTS};
 use crate::prelude::*;
@@ -20,11 +21,40 @@ impl Day for Day22 {
         true
     }
 
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(Generator::input(rng, size))
+    }
+
     fn parse(input: &'static str, _part: Part) -> Result<Self::Parsed> {
         Ok(Parser::input(input)?.1)
     }
 
     fn part1(parsed: &Self::Parsed) -> Result<Self::Output> {
+        Ok(Self::disintegrable(parsed, &mut ()))
+    }
+
+    fn part2(_parsed: &Self::Parsed) -> Result<Self::Output> {
+        Err(Error::PartNotImplemented(2))?
+    }
+
+    fn observe(
+        parsed: &Self::Parsed,
+        part: Part,
+        observer: &mut dyn Observer,
+    ) -> Result<Option<Self::Output>> {
+        Ok(match part {
+            Part1 => Some(Self::disintegrable(parsed, observer)),
+            Part2 => None,
+        })
+    }
+}
+
+impl Day22 {
+    /// The number of bricks that can be disintegrated after the bricks have settled.
+    fn disintegrable<O: Observer + ?Sized>(
+        parsed: &<Self as Day>::Parsed,
+        observer: &mut O,
+    ) -> <Self as Day>::Output {
         let mut unsettled = parsed.clone();
         unsettled.sort_by_key(|brick| brick.bottom());
 
@@ -41,7 +71,7 @@ impl Day for Day22 {
             supporting: Vec::new(),
         }];
 
-        for brick in unsettled.into_iter() {
+        for (index, brick) in unsettled.into_iter().enumerate() {
             let mut highest = 0;
             for settled_brick in settled.iter_mut() {
                 if brick.above(&settled_brick.brick)
@@ -51,20 +81,27 @@ impl Day for Day22 {
                 }
             }
             let mut next = None;
+            let mut supported_by = 0;
             for settled_brick in settled.iter_mut() {
                 if brick.above(&settled_brick.brick)
                     && settled_brick.brick.top() - settled_brick.offset == highest
                 {
                     next = Some(brick.settle(settled_brick));
+                    supported_by += 1;
                 }
             }
 
             if let Some(settled_brick) = next {
+                observer.observe(&Event::Settled {
+                    brick: index,
+                    fall: settled_brick.offset,
+                    supported_by,
+                });
                 settled.push(settled_brick);
             }
         }
 
-        Ok(settled
+        settled
             .iter()
             .filter(|settled_brick| {
                 settled_brick.supporting.iter().all(|settled_brick2| {
@@ -74,15 +111,11 @@ impl Day for Day22 {
                     })
                 })
             })
-            .count())
-    }
-
-    fn part2(_parsed: &Self::Parsed) -> Result<Self::Output> {
-        todo!()
+            .count()
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub struct Coordinate {
     x: usize,
     y: usize,
@@ -95,7 +128,7 @@ impl Coordinate {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub struct Brick {
     start: Coordinate,
     end: Coordinate,
@@ -152,13 +185,24 @@ impl Brick {
 struct Parser;
 impl Parser {
     fn input(s: &'static str) -> IResult<<Day22 as Day>::Parsed> {
-        all_consuming(separated_list1(line_ending, Parser::brick))(s)
+        all_consuming(lines("a brick like \"1,0,1~1,2,1\"", Parser::brick))(s)
     }
 
     fn brick(s: &'static str) -> IResult<Brick> {
-        map(
-            separated_pair(Parser::coordinate, tag("~"), Parser::coordinate),
-            |(start, end)| Brick { start, end },
+        context(
+            "a brick above the ground, with its start before its end",
+            verify(
+                map(
+                    separated_pair(Parser::coordinate, tag("~"), Parser::coordinate),
+                    |(start, end)| Brick { start, end },
+                ),
+                |brick| {
+                    brick.bottom() > 0
+                        && brick.left() <= brick.right()
+                        && brick.front() <= brick.back()
+                        && brick.bottom() <= brick.top()
+                },
+            ),
         )(s)
     }
 
@@ -169,3 +213,23 @@ impl Parser {
         )(s)
     }
 }
+
+struct Generator;
+impl Generator {
+    /// Bricks on a small area that don't overlap, because each brick gets its own levels.
+    fn input(rng: &mut Rng, size: usize) -> String {
+        let mut bottom = 1;
+        (0..size)
+            .map(|_| {
+                let (x, y) = (rng.usize(0..10), rng.usize(0..10));
+                let mut end = [x, y, bottom];
+                end[rng.usize(0..3)] += rng.usize(0..4);
+
+                let brick = format!("{x},{y},{bottom}~{},{},{}", end[0], end[1], end[2]);
+                bottom = end[2] + rng.usize(1..=3);
+                brick
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
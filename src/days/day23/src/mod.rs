@@ -3,9 +3,11 @@ use std::collections::HashMap;
 use nom::{
     branch::alt,
     character::complete::{char, line_ending},
-    combinator::{all_consuming, map},
-    multi::{many1, separated_list1},
+    combinator::{all_consuming, map, verify},
+    error::context,
+    multi::separated_list1,
 };
+use serde::Serialize;
 
 use super::inputs::{Inputs, INPUTS};
 use crate::prelude::*;
@@ -23,29 +25,66 @@ impl Day for Day23 {
         true
     }
 
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(Generator::input(rng, size))
+    }
+
     fn parse(input: &'static str, _part: Part) -> Result<Self::Parsed> {
         Ok(Parser::input(input)?.1)
     }
 
     fn part1(parsed: &Self::Parsed) -> Result<Self::Output> {
         parsed
-            .longest_path(Node::default())
+            .longest_path(Node::default(), false)
+            .map(|(length, _)| length)
             .context("Longest path not found")
     }
 
     fn part2(parsed: &Self::Parsed) -> Result<Self::Output> {
         let graph = Graph::from(parsed);
-        graph
-            .longest_path(&GraphNode::default())
+        let longest_path = graph.longest_path(&GraphNode::default(), false);
+        cancel::check()?;
+        longest_path
+            .map(|(length, _)| length)
             .context("Longest path not found")
     }
+
+    fn render(parsed: &Self::Parsed, part: Part) -> Result<Option<Box<dyn Render + '_>>> {
+        let tiles = match part {
+            Part1 => parsed
+                .longest_path(Node::default(), true)
+                .map(|(_, tiles)| tiles),
+            Part2 => Graph::from(parsed)
+                .longest_path(&GraphNode::default(), true)
+                .and_then(|(_, junctions)| {
+                    junctions
+                        .windows(2)
+                        .try_fold(Vec::new(), |mut tiles, pair| {
+                            tiles.extend(parsed.corridor(pair[0], pair[1])?);
+                            Some(tiles)
+                        })
+                }),
+        };
+        cancel::check()?;
+        Ok(Some(Box::new(Hike {
+            map: parsed,
+            tiles: tiles.context("Longest path not found")?,
+        })))
+    }
 }
 
+#[derive(Serialize)]
 pub struct Map(Vec<Vec<char>>);
 
 #[derive(Debug, Clone)]
 pub struct Graph(HashMap<Tile, HashMap<Tile, usize>>);
 
+/// The tiles of the longest hike, for `--render`.
+struct Hike<'a> {
+    map: &'a Map,
+    tiles: Vec<Tile>,
+}
+
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 struct Tile {
     x: usize,
@@ -114,15 +153,52 @@ impl Direction {
 }
 
 impl Map {
-    fn longest_path(&self, node: Node) -> Option<<Day23 as Day>::Output> {
+    /// The length of the longest hike, with its tiles from the end to the start if `tiles` is set.
+    fn longest_path(&self, node: Node, tiles: bool) -> Option<(<Day23 as Day>::Output, Vec<Tile>)> {
+        // Without slopes leading away from junctions the hike can go around in circles, but a hike
+        // that doesn't can't be longer than the number of tiles
+        if cancel::is_cancelled() || node.length > self.0.len() * self.0[0].len() {
+            return None;
+        }
         if node.tile == self.end() {
-            return Some(0);
+            return Some((0, if tiles { vec![node.tile] } else { Vec::new() }));
         }
-        self.successors(node, Part1)
+        let (length, mut hike) = self
+            .successors(node, Part1)
             .into_iter()
-            .filter_map(|successor| self.longest_path(successor))
-            .max()
-            .map(|n| n + 1)
+            .filter_map(|successor| self.longest_path(successor, tiles))
+            .max_by_key(|(length, _)| *length)?;
+        if tiles {
+            hike.push(node.tile);
+        }
+        Some((length + 1, hike))
+    }
+
+    /// The tiles of the corridor between neighbouring junctions `from` and `to`.
+    fn corridor(&self, from: Tile, to: Tile) -> Option<Vec<Tile>> {
+        let start = Node {
+            tile: from,
+            last: from,
+            ..Node::default()
+        };
+        // Successors never go back, so try two opposite directions to leave in any direction
+        [Down, Up]
+            .into_iter()
+            .flat_map(|direction| self.successors(Node { direction, ..start }, Part2))
+            .find_map(|mut node| {
+                let mut tiles = vec![from];
+                while tiles.len() <= self.0.len() * self.0[0].len() {
+                    tiles.push(node.tile);
+                    if node.tile == to {
+                        return Some(tiles);
+                    }
+                    match self.successors(node, Part2)[..] {
+                        [next] => node = next,
+                        _ => return None,
+                    }
+                }
+                None
+            })
     }
 
     fn successors(
@@ -175,7 +251,7 @@ impl Map {
                 let next_c = self.get(next_x, next_y);
                 next_direction != direction.opposite()
                     && match part {
-                        Part1 => next_c == '.' || next_c == next_direction.into(),
+                        Part1 => next_c == '.' || next_c == char::from(next_direction),
                         Part2 => next_c != '#',
                     }
             },
@@ -191,11 +267,11 @@ impl Map {
     }
 
     fn get(&self, x: usize, y: usize) -> char {
-        if y == 0 || y > self.0.len() {
-            '#'
-        } else {
-            self.0[y - 1][x - 1]
-        }
+        self.0
+            .get(y.wrapping_sub(1))
+            .and_then(|row| row.get(x.wrapping_sub(1)))
+            .copied()
+            .unwrap_or('#')
     }
 }
 
@@ -261,14 +337,25 @@ impl From<&Map> for Graph {
 }
 
 impl Graph {
-    fn longest_path(&self, node: &GraphNode) -> Option<usize> {
-        if node.tile == self.end() {
-            Some(0)
+    /// The length of the longest route, with its junctions if `junctions` is set.
+    fn longest_path(&self, node: &GraphNode, junctions: bool) -> Option<(usize, Vec<Tile>)> {
+        if cancel::is_cancelled() {
+            None
+        } else if Some(node.tile) == self.end() {
+            let mut route = Vec::new();
+            if junctions {
+                route.extend(&node.visited);
+                route.push(node.tile);
+            }
+            Some((0, route))
         } else {
             self.successors(node)
                 .into_iter()
-                .filter_map(|(successor, length)| self.longest_path(&successor).map(|n| n + length))
-                .max()
+                .filter_map(|(successor, length)| {
+                    self.longest_path(&successor, junctions)
+                        .map(|(n, route)| (n + length, route))
+                })
+                .max_by_key(|(n, _)| *n)
         }
     }
 
@@ -277,8 +364,8 @@ impl Graph {
         successor_visited.push(node.tile);
         self.0
             .get(&node.tile)
-            .unwrap()
-            .iter()
+            .into_iter()
+            .flatten()
             .filter_map(|(successor, length)| {
                 if !node.visited.contains(successor) {
                     Some((
@@ -295,9 +382,33 @@ impl Graph {
             .collect()
     }
 
-    fn end(&self) -> Tile {
-        let y = self.0.keys().map(|tile| tile.y).max().unwrap();
-        *self.0.keys().find(|tile| tile.y == y).unwrap()
+    fn end(&self) -> Option<Tile> {
+        let y = self.0.keys().map(|tile| tile.y).max()?;
+        self.0.keys().find(|tile| tile.y == y).copied()
+    }
+}
+
+impl Render for Hike<'_> {
+    fn render(&self) -> Image {
+        let mut image = Image::new(self.map.0[0].len() as f64, self.map.0.len() as f64);
+        for (y, row) in self.map.0.iter().enumerate() {
+            for (x, c) in row.iter().enumerate() {
+                if *c == '#' {
+                    image.tile(x, y, Rgb::GREEN);
+                } else if *c != '.' {
+                    image.tile(x, y, Rgb::GRAY);
+                }
+            }
+        }
+        image.polyline(
+            self.tiles
+                .iter()
+                .map(|tile| (tile.x as f64 - 0.5, tile.y as f64 - 0.5))
+                .collect(),
+            Rgb::RED,
+            0.5,
+        );
+        image
     }
 }
 
@@ -305,7 +416,16 @@ struct Parser;
 impl Parser {
     fn input(s: &'static str) -> IResult<<Day23 as Day>::Parsed> {
         map(
-            all_consuming(separated_list1(line_ending, many1(Parser::tile))),
+            all_consuming(context(
+                "rows of the same length",
+                verify(
+                    separated_list1(
+                        line_ending,
+                        tiles("a tile '.', '#', '^', '>', 'v' or '<'", Parser::tile),
+                    ),
+                    |rows: &Vec<Vec<char>>| rows.iter().all(|row| row.len() == rows[0].len()),
+                ),
+            )),
             Map,
         )(s)
     }
@@ -321,3 +441,41 @@ impl Parser {
         ))(s)
     }
 }
+
+struct Generator;
+impl Generator {
+    /// A lattice of `size + 1` by `size + 1` junctions like in the real input, with slopes that
+    /// lead right and down. Some corridors are left out, so the hike takes other routes.
+    fn input(rng: &mut Rng, size: usize) -> String {
+        const SPACING: usize = 4;
+        let junctions = size + 1;
+        let length = SPACING * (junctions - 1) + 3;
+        let mut map = vec![vec!['#'; length]; length];
+
+        for a in 0..junctions {
+            for b in 0..junctions {
+                let (x, y) = (1 + SPACING * a, 1 + SPACING * b);
+                map[y][x] = '.';
+
+                // The corridors along the edges are always there, so the hike can leave the
+                // start and reach the end from every junction
+                let mut keep = |a: usize| a == 0 || a == junctions - 1 || rng.u8(0..5) != 0;
+                if a + 1 < junctions && keep(b) {
+                    map[y][x + 1..x + SPACING].copy_from_slice(&['>', '.', '>']);
+                }
+                if b + 1 < junctions && keep(a) {
+                    for (dy, tile) in ['v', '.', 'v'].into_iter().enumerate() {
+                        map[y + 1 + dy][x] = tile;
+                    }
+                }
+            }
+        }
+
+        map[0][1] = '.';
+        map[length - 1][length - 2] = '.';
+        map.into_iter()
+            .map(String::from_iter)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
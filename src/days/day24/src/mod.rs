@@ -1,10 +1,10 @@
 use nom::{
     bytes::complete::tag,
-    character::complete::{i64, line_ending, space0},
+    character::complete::{i64, space0},
     combinator::{all_consuming, map},
-    multi::separated_list1,
     sequence::{preceded, separated_pair, tuple},
 };
+use serde::Serialize;
 
 use super::inputs::{Inputs, INPUTS};
 use crate::prelude::*;
@@ -79,11 +79,11 @@ impl Day for Day24 {
     }
 
     fn part2(_parsed: &Self::Parsed) -> Result<Self::Output> {
-        todo!()
+        Err(Error::PartNotImplemented(2))?
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize)]
 pub struct Coordinates {
     x: f64,
     y: f64,
@@ -93,8 +93,8 @@ pub struct Coordinates {
 struct Parser;
 impl Parser {
     fn input(s: &'static str) -> IResult<<Day24 as Day>::Parsed> {
-        all_consuming(separated_list1(
-            line_ending,
+        all_consuming(lines(
+            "a hailstone like \"19, 13, 30 @ -2, 1, -2\"",
             separated_pair(
                 Parser::coordinates,
                 preceded(space0, tag("@")),
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use nom::{
     bytes::complete::tag,
-    character::complete::{alpha1, line_ending, space1},
+    character::complete::{alpha1, space1},
     combinator::{all_consuming, map},
     multi::separated_list1,
     sequence::separated_pair,
@@ -15,6 +15,16 @@ use crate::prelude::*;
 pub struct Day25;
 impl Day for Day25 {
     const INPUTS: Self::Inputs = INPUTS;
+    const INPUT_SPECIFIC: &'static [InputSpecific] = &[InputSpecific {
+        part: Part1,
+        fingerprints: &[Fingerprint::of(INPUTS[0]), Fingerprint::of(INPUTS[1])],
+        reason: "the wires to cut are hard-coded for my input and the example",
+    }];
+    const VARIANTS: &'static [Variant<Self::Parsed, Self::Output>] = &[Variant {
+        name: "search",
+        part: Part1,
+        solve: |parsed| SnowProducer::from(parsed).search_wires(),
+    }];
     type Inputs = Inputs;
     type Parsed = HashMap<Component, Vec<Component>>;
     type Output = usize;
@@ -41,20 +51,17 @@ pub type Component = &'static str;
 type Wire = (Component, Component);
 
 struct SnowProducer {
-    #[cfg(test)]
     wires: Vec<Wire>,
     successors: HashMap<Component, Vec<Component>>,
 }
 
 impl From<&<Day25 as Day>::Parsed> for SnowProducer {
     fn from(parsed: &<Day25 as Day>::Parsed) -> Self {
-        #[cfg(test)]
         let mut wires = Vec::new();
 
         let mut successors = parsed.clone();
         for (source, destinations) in parsed {
             for destination in destinations {
-                #[cfg(test)]
                 wires.push((*source, *destination));
 
                 successors
@@ -64,23 +71,46 @@ impl From<&<Day25 as Day>::Parsed> for SnowProducer {
             }
         }
 
-        #[cfg(test)]
-        return Self { wires, successors };
-
-        #[cfg(not(test))]
-        return Self { successors };
+        Self { wires, successors }
     }
 }
 
 impl SnowProducer {
-    #[cfg(test)]
+    /// Cut the wires found by hand for my input or the example.
     fn find_wires(&self) -> Result<usize> {
+        // For the real input the search will take approx. a year to compute
+        // I used [graphviz](graphviz.svg) in docker to find the 3 wires.
+        // The normal layout isn't really usable, so use layout neato.
+        // Command: `dot -Tsvg graphviz.txt -Kneato > graphviz.svg`
+        // Let's only take these into account. The wires of the example are given in the puzzle.
+        const WIRES: [[Wire; 3]; 2] = [
+            [("fsv", "spx"), ("tvj", "cvx"), ("kdk", "nct")],
+            [("hfx", "pzl"), ("bvb", "cmg"), ("nvd", "jqt")],
+        ];
+
+        let known = |&(a, b): &Wire| self.wires.contains(&(a, b)) || self.wires.contains(&(b, a));
+        for [wire1, wire2, wire3] in WIRES {
+            if [wire1, wire2, wire3].iter().all(known) {
+                if let Some(left) = self.separated(wire1, wire2, wire3)? {
+                    let right = self.successors.keys().count() - left;
+                    return Ok(left * right);
+                }
+            }
+        }
+
+        bail!("The hard-coded wires don't separate the graph, try --variant search")
+    }
+
+    /// Try every combination of three wires. Far too slow for the real input. Cutting the wires of
+    /// a component with only three wires also separates the graph, so take the most even split.
+    fn search_wires(&self) -> Result<usize> {
+        let mut best = None;
         for (k, wire1) in self
             .wires
             .iter()
             .copied()
             .enumerate()
-            .take(self.wires.len() - 2)
+            .take(self.wires.len().saturating_sub(2))
         {
             for (l, wire2) in self
                 .wires
@@ -90,36 +120,17 @@ impl SnowProducer {
                 .skip(k + 1)
                 .take(self.wires.len() - 1)
             {
+                cancel::check()?;
                 for wire3 in self.wires.iter().copied().skip(l + 1) {
                     if let Some(left) = self.separated(wire1, wire2, wire3)? {
                         let right = self.successors.keys().count() - left;
-                        return Ok(left * right);
+                        best = best.max(Some(left * right));
                     }
                 }
             }
         }
 
-        Err(anyhow!("Wires not found"))
-    }
-
-    #[cfg(not(test))]
-    fn find_wires(&self) -> Result<usize> {
-        // For the real input the full approach will take approx. a year to compute
-        // I used [graphviz](graphviz.svg) in docker to find the 3 wires.
-        // The normal layout isn't really usable, so use layout neato.
-        // Command: `dot -Tsvg graphviz.txt -Kneato > graphviz.svg`
-        // Let's only take these into account.
-
-        let wire1 = ("fsv", "spx");
-        let wire2 = ("tvj", "cvx");
-        let wire3 = ("kdk", "nct");
-
-        if let Some(left) = self.separated(wire1, wire2, wire3)? {
-            let right = self.successors.keys().count() - left;
-            return Ok(left * right);
-        }
-
-        Err(anyhow!("Wires not found"))
+        best.ok_or_else(|| anyhow!("Wires not found"))
     }
 
     fn separated(&self, wire1: Wire, wire2: Wire, wire3: Wire) -> Result<Option<usize>> {
@@ -187,8 +198,8 @@ struct Parser;
 impl Parser {
     fn input(s: &'static str) -> IResult<<Day25 as Day>::Parsed> {
         map(
-            all_consuming(separated_list1(
-                line_ending,
+            all_consuming(lines(
+                "a component like \"jqt: rhn xhk nvd\"",
                 separated_pair(alpha1, tag(": "), separated_list1(space1, alpha1)),
             )),
             HashMap::from_iter,
@@ -14,5 +14,21 @@ pub struct Example {
 
 pub struct Part {
     pub part: crate::Part,
+    /// The expected answer, or part of the expected error message if `error` is set
     pub expected: &'static str,
+    pub error: bool,
+}
+
+/// The expected answer of a part of an example.
+#[cfg(test)]
+pub(crate) fn expected(day: u8, example: usize, part: crate::Part) -> &'static str {
+    crate::days::DAYS
+        .iter()
+        .filter(|def| def.day == day)
+        .flat_map(|def| def.examples)
+        .filter(|def| def.example == example)
+        .flat_map(|def| def.parts)
+        .find(|def| def.part == part && !def.error)
+        .map(|def| def.expected)
+        .unwrap()
 }
@@ -0,0 +1,67 @@
+//! Encrypted inputs, so inputs can be committed without publishing them. An `input.txt.enc` next
+//! to a missing `input.txt` is decrypted by the build script with the passphrase in
+//! `AOC_INPUT_KEY`, or in the file in `AOC_INPUT_KEY_FILE`, by default `~/.config/aoc/input-key`.
+
+use std::{fs, path::PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::watch::input_path;
+
+mod cipher;
+
+pub use cipher::{decrypt, encrypt, key_file, passphrase, KEY_FILE_VAR, KEY_VAR};
+
+/// Whether the input of `day` is encrypted and couldn't be decrypted when building, so it is
+/// missing from the binary.
+pub fn locked(day: u8) -> bool {
+    env!("AOC_LOCKED_INPUTS")
+        .split(',')
+        .any(|locked| locked.parse() == Ok(day))
+}
+
+/// Whether `day` had neither an input nor an encrypted input when building, so it is missing from
+/// the binary.
+pub fn missing(day: u8) -> bool {
+    env!("AOC_MISSING_INPUTS")
+        .split(',')
+        .any(|missing| missing.parse() == Ok(day))
+}
+
+/// The encrypted input file of a day in this repository.
+pub fn encrypted_path(day: u8) -> PathBuf {
+    input_path(day, 0).with_extension("txt.enc")
+}
+
+/// Encrypt `input.txt` of a day to `input.txt.enc` with the input key.
+pub fn encrypt_input(day: u8) -> Result<PathBuf> {
+    let passphrase = passphrase()?.with_context(|| {
+        format!(
+            "No input key, set {KEY_VAR} or save a passphrase in {}",
+            key_file().unwrap_or_default().display()
+        )
+    })?;
+    let path = input_path(day, 0);
+    let input =
+        fs::read(&path).with_context(|| format!("Cannot read input file {}", path.display()))?;
+
+    let encrypted_path = encrypted_path(day);
+    fs::write(&encrypted_path, encrypt(&passphrase, &input)?)?;
+    Ok(encrypted_path)
+}
+
+#[cfg(test)]
+#[test]
+fn inputs_are_decrypted_with_the_same_key_only() {
+    let input = b"467..114..\n...*......";
+    let encrypted = encrypt("secret", input).unwrap();
+
+    assert!(!encrypted.windows(input.len()).any(|window| window == input));
+    assert_eq!(decrypt("secret", &encrypted).unwrap(), input);
+    assert!(decrypt("wrong", &encrypted).is_err());
+    assert!(decrypt("secret", &encrypted[..20]).is_err());
+    assert_ne!(encrypt("secret", input).unwrap(), encrypted);
+
+    let error = decrypt("secret", input).unwrap_err();
+    assert_eq!(error.to_string(), "Not an encrypted input");
+}
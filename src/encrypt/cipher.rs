@@ -0,0 +1,85 @@
+//! Encryption of inputs with a passphrase. Shared with the build script, which decrypts the inputs
+//! before they are embedded, so this only depends on `std`, `anyhow` and the cipher crates.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key, Nonce,
+};
+
+/// Environment variable with the passphrase of the encrypted inputs.
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+/// Environment variable with the path of a file containing the passphrase.
+pub const KEY_FILE_VAR: &str = "AOC_INPUT_KEY_FILE";
+
+/// Start of every encrypted input, followed by the salt of the key, the nonce and the ciphertext.
+const MAGIC: &[u8] = b"aoc2023-input-v2\n";
+const SALT_SIZE: usize = 16;
+const NONCE_SIZE: usize = 12;
+
+/// The file with the passphrase: `AOC_INPUT_KEY_FILE`, by default `~/.config/aoc/input-key`.
+pub fn key_file() -> Option<PathBuf> {
+    match env::var_os(KEY_FILE_VAR) {
+        Some(path) => Some(PathBuf::from(path)),
+        None => env::var_os("HOME").map(|home| {
+            Path::new(&home)
+                .join(".config")
+                .join("aoc")
+                .join("input-key")
+        }),
+    }
+}
+
+/// The passphrase from `AOC_INPUT_KEY` or the key file, if any.
+pub fn passphrase() -> Result<Option<String>> {
+    if let Ok(passphrase) = env::var(KEY_VAR) {
+        return Ok(Some(passphrase.trim().to_string()));
+    }
+    match key_file().filter(|path| path.exists()) {
+        Some(path) => {
+            let passphrase = fs::read_to_string(&path)
+                .with_context(|| format!("Cannot read key file {}", path.display()))?;
+            Ok(Some(passphrase.trim().to_string()))
+        }
+        None => Ok(None),
+    }
+}
+
+pub fn encrypt(passphrase: &str, input: &[u8]) -> Result<Vec<u8>> {
+    let mut salt = [0; SALT_SIZE];
+    OsRng.fill_bytes(&mut salt);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher(passphrase, &salt)?
+        .encrypt(&nonce, input)
+        .map_err(|_| anyhow!("Cannot encrypt input"))?;
+    Ok([MAGIC, &salt, nonce.as_slice(), &ciphertext].concat())
+}
+
+pub fn decrypt(passphrase: &str, encrypted: &[u8]) -> Result<Vec<u8>> {
+    let Some(encrypted) = encrypted.strip_prefix(MAGIC) else {
+        bail!("Not an encrypted input");
+    };
+    if encrypted.len() < SALT_SIZE + NONCE_SIZE {
+        bail!("Encrypted input is truncated");
+    }
+    let (salt, encrypted) = encrypted.split_at(SALT_SIZE);
+    let (nonce, ciphertext) = encrypted.split_at(NONCE_SIZE);
+    cipher(passphrase, salt)?
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow!("Wrong key, or the encrypted input is damaged"))
+}
+
+/// The key is derived with Argon2id, so guessing the passphrase of a committed input is slow.
+fn cipher(passphrase: &str, salt: &[u8]) -> Result<ChaCha20Poly1305> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|error| anyhow!("Cannot derive the key: {error}"))?;
+    Ok(ChaCha20Poly1305::new(&key))
+}
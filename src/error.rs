@@ -1,8 +1,216 @@
+use nom::error::{ErrorKind, VerboseError, VerboseErrorKind};
+
+use crate::fingerprint::Fingerprint;
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("Day {0} is not implemented")]
     DayNotImplemented(u8),
 
+    #[error("Part {0} is not implemented")]
+    PartNotImplemented(u8),
+
     #[error("Example {0} is not found")]
     ExampleNotFound(usize),
+
+    #[error(
+        "Input of day {0} is encrypted and was not decrypted when building, set {} or save the key in {}, and rebuild",
+        crate::encrypt::KEY_VAR,
+        crate::encrypt::key_file().unwrap_or_default().display()
+    )]
+    InputLocked(u8),
+
+    #[error("Input of day {0} is missing, save it in src/days/day{0:02}/inputs/input.txt or download it with fetch, and rebuild")]
+    InputMissing(u8),
+
+    #[error("Solver variant {0} is not found")]
+    VariantNotFound(String),
+
+    #[error("Cancelled")]
+    Cancelled,
+
+    #[error("Worker thread for day {0} panicked")]
+    WorkerPanicked(u8),
+
+    #[error("Part {part} only works for specific inputs ({reason}), not for input {fingerprint}")]
+    InputSpecific {
+        part: u8,
+        reason: &'static str,
+        fingerprint: Fingerprint,
+    },
+
+    #[error("{0}")]
+    Parse(ParseError),
+}
+
+/// A parse error with its position in the input.
+#[derive(Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+    pub expected: String,
+    pub found: Option<char>,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Parse error at line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )?;
+        match self.found {
+            Some(found) => write!(f, ", found {found:?}")?,
+            None => write!(f, ", found end of input")?,
+        }
+        write!(
+            f,
+            "\n  {}\n  {}^",
+            self.snippet,
+            " ".repeat(self.column - 1)
+        )
+    }
+}
+
+impl ParseError {
+    /// Replace a nom error returned by `Day::parse` with a [`ParseError`] pointing into `input`.
+    /// Other errors are returned unchanged.
+    pub fn locate(error: anyhow::Error, input: &str) -> anyhow::Error {
+        let error = match error.downcast::<nom::Err<VerboseError<&'static str>>>() {
+            Ok(nom::Err::Error(verbose) | nom::Err::Failure(verbose)) => {
+                return match verbose.errors.first() {
+                    Some((rest, kind)) => {
+                        let expected = verbose
+                            .errors
+                            .iter()
+                            .find_map(|(_, kind)| match kind {
+                                VerboseErrorKind::Context(context) => Some(context.to_string()),
+                                _ => None,
+                            })
+                            .unwrap_or_else(|| describe(kind));
+                        Self::at(input, rest, expected).into()
+                    }
+                    None => anyhow::anyhow!("Parse error"),
+                };
+            }
+            Ok(incomplete) => return incomplete.into(),
+            Err(error) => error,
+        };
+
+        match error.downcast::<nom::Err<nom::error::Error<&'static str>>>() {
+            Ok(nom::Err::Error(simple) | nom::Err::Failure(simple)) => {
+                Self::at(input, simple.input, expected(simple.code)).into()
+            }
+            Ok(incomplete) => incomplete.into(),
+            Err(error) => error,
+        }
+    }
+
+    fn at(input: &str, rest: &str, expected: String) -> Error {
+        // The rest of the input is a subslice of the input, unless a day parses a copy
+        let offset = (rest.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| *offset <= input.len() && input.is_char_boundary(*offset))
+            .unwrap_or(input.len().saturating_sub(rest.len()));
+
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        Error::Parse(ParseError {
+            line: before.matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            expected,
+            found: input[offset..].chars().next(),
+        })
+    }
+}
+
+fn describe(kind: &VerboseErrorKind) -> String {
+    match kind {
+        VerboseErrorKind::Char(c) => format!("{c:?}"),
+        VerboseErrorKind::Context(context) => context.to_string(),
+        VerboseErrorKind::Nom(kind) => expected(*kind),
+    }
+}
+
+/// What the parser that failed with `kind` expected, for parsers without a context.
+fn expected(kind: ErrorKind) -> String {
+    match kind {
+        ErrorKind::Eof => "end of input",
+        ErrorKind::Tag => "a fixed text",
+        ErrorKind::IsA | ErrorKind::OneOf => "one of the allowed characters",
+        ErrorKind::IsNot | ErrorKind::NoneOf => "a character that isn't excluded",
+        ErrorKind::TakeWhile1 | ErrorKind::TakeWhileMN | ErrorKind::TakeUntil => {
+            "characters of the expected kind"
+        }
+        ErrorKind::Many1 | ErrorKind::Many1Count | ErrorKind::SeparatedList => "at least one item",
+        ErrorKind::ManyMN | ErrorKind::Count => "more items",
+        ErrorKind::ManyTill => "an item or the end of the list",
+        ErrorKind::Digit => "a number",
+        ErrorKind::HexDigit => "a hexadecimal number",
+        ErrorKind::Alpha => "a letter",
+        ErrorKind::AlphaNumeric => "a letter or digit",
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace",
+        ErrorKind::CrLf => "a line ending",
+        ErrorKind::Char => "a specific character",
+        ErrorKind::Float => "a floating point number",
+        ErrorKind::Alt => "one of the alternatives",
+        ErrorKind::Verify | ErrorKind::MapRes | ErrorKind::MapOpt => "a valid value",
+        kind => return kind.description().to_lowercase(),
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+#[test]
+fn parse_error_points_at_line_and_column() {
+    use nom::{character::complete::char, combinator::all_consuming, multi::many1};
+
+    let input = "..\n.#.";
+    let error = all_consuming(many1(nom::branch::alt((char('.'), char('\n')))))(input)
+        .map_err(|error: nom::Err<VerboseError<&'static str>>| anyhow::Error::from(error))
+        .unwrap_err();
+
+    match ParseError::locate(error, input).downcast::<Error>() {
+        Ok(Error::Parse(parse_error)) => {
+            assert_eq!((parse_error.line, parse_error.column), (2, 2));
+            assert_eq!(parse_error.snippet, ".#.");
+            assert_eq!(parse_error.found, Some('#'));
+        }
+        _ => panic!("Expected a parse error"),
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn day_parse_errors_point_at_the_offending_token() {
+    let locate = |day, input| match crate::run_day(day, input, true, false)
+        .unwrap_err()
+        .downcast::<Error>()
+    {
+        Ok(Error::Parse(parse_error)) => parse_error,
+        error => panic!("Expected a parse error, got {error:?}"),
+    };
+
+    // An unknown tile in a contraption
+    let error = locate(16, ".|.\n|X-\n...");
+    assert_eq!((error.line, error.column, error.found), (2, 2, Some('X')));
+    assert!(error.expected.contains("tile"), "{error}");
+
+    // A malformed rule
+    let error = locate(19, "px{a<20xx:qkq,rfg}\n\n{x=787,m=2655,a=1222,s=2876}");
+    assert_eq!((error.line, error.column, error.found), (1, 8, Some('x')));
+    assert_eq!(error.expected, "':' and a destination");
+
+    // A line that is only partly valid
+    let error = locate(12, "#.# 1,1\n#?# 1,x");
+    assert_eq!((error.line, error.column, error.found), (2, 6, Some(',')));
+    assert!(error.expected.contains("springs and groups"), "{error}");
 }
@@ -0,0 +1,304 @@
+//! Extracting examples and their expected answers from a saved puzzle page, for the `extract`
+//! subcommand.
+//!
+//! A puzzle page has an `<article class="day-desc">` per unlocked part. Examples are the
+//! `<pre><code>` blocks, and the answers of examples are emphasised as `<code><em>` in the text,
+//! the last one usually being the answer for the whole example.
+
+use anyhow::{bail, Context, Result};
+
+/// The description of a part on a puzzle page.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Article {
+    /// Example blocks, without the trailing newline
+    pub blocks: Vec<String>,
+    /// Emphasised code in the text, in order
+    pub answers: Vec<String>,
+}
+
+/// A saved puzzle page.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Puzzle {
+    /// The day in the title, if found
+    pub day: Option<u8>,
+    /// The articles of the unlocked parts, part 1 first
+    pub articles: Vec<Article>,
+}
+
+/// The example of a part, as suggested or confirmed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assignment {
+    pub part: u8,
+    /// Index in [`Puzzle::blocks`]
+    pub block: usize,
+    pub expected: String,
+}
+
+impl Puzzle {
+    pub fn parse(html: &str) -> Result<Puzzle> {
+        let day = between(html, "--- Day ", ":")
+            .next()
+            .and_then(|day| day.trim().parse().ok());
+
+        let mut articles: Vec<_> = between(html, "<article class=\"day-desc\">", "</article>")
+            .map(Article::parse)
+            .collect();
+        if articles.is_empty() {
+            articles.push(Article::parse(html));
+        }
+        if articles.iter().all(|article| article.blocks.is_empty()) {
+            bail!("No <pre><code> example blocks found");
+        }
+
+        Ok(Puzzle { day, articles })
+    }
+
+    /// All example blocks, in order of appearance.
+    pub fn blocks(&self) -> Vec<&str> {
+        self.articles
+            .iter()
+            .flat_map(|article| article.blocks.iter().map(String::as_str))
+            .collect()
+    }
+
+    /// A guess of the example and answer of every part with an emphasised answer: the last
+    /// emphasised code is the answer, and the last block up to its article is the example.
+    pub fn suggest(&self) -> Vec<Assignment> {
+        let mut blocks = 0;
+        let mut assignments = Vec::new();
+        for (index, article) in self.articles.iter().enumerate() {
+            blocks += article.blocks.len();
+            if let (Some(expected), Some(block)) = (article.answers.last(), blocks.checked_sub(1)) {
+                assignments.push(Assignment {
+                    part: index as u8 + 1,
+                    block,
+                    expected: expected.clone(),
+                });
+            }
+        }
+        assignments
+    }
+}
+
+impl Article {
+    fn parse(html: &str) -> Article {
+        Article {
+            blocks: between(html, "<pre><code>", "</code></pre>")
+                .map(|block| text(block).trim_end_matches('\n').to_string())
+                .collect(),
+            answers: between(html, "<code><em>", "</em></code>")
+                .map(text)
+                .collect(),
+        }
+    }
+}
+
+/// The parts of `html` between every `start` and the following `end`.
+fn between<'a>(html: &'a str, start: &'a str, end: &'a str) -> impl Iterator<Item = &'a str> {
+    html.split(start)
+        .skip(1)
+        .filter_map(move |rest| rest.find(end).map(|index| &rest[..index]))
+}
+
+/// The text of an HTML fragment: tags are removed and entities decoded.
+fn text(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+    while let Some(index) = rest.find(['<', '&']) {
+        text += &rest[..index];
+        rest = &rest[index..];
+        if rest.starts_with('<') {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+        } else {
+            let Some(end) = rest.find(';').filter(|end| *end <= 8) else {
+                text.push('&');
+                rest = &rest[1..];
+                continue;
+            };
+            match entity(&rest[1..end]) {
+                Some(c) => text.push(c),
+                None => text += &rest[..=end],
+            }
+            rest = &rest[end + 1..];
+        }
+    }
+    text + rest
+}
+
+fn entity(name: &str) -> Option<char> {
+    match name {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        _ => {
+            let code = match name.strip_prefix("#x").or(name.strip_prefix("#X")) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => name.strip_prefix('#')?.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+/// The `inputs/mod.rs` of a day with `examples` example files. The input is embedded from where
+/// the build script put it.
+pub fn inputs_module(day: u8, examples: usize) -> String {
+    let mut module = format!(
+        "pub type Inputs = [&'static str; {}];\npub const INPUTS: Inputs = [\n    include_str!(concat!(env!(\"OUT_DIR\"), \"/day{day:02}/input.txt\")),\n",
+        examples + 1
+    );
+    for example in 1..=examples {
+        module += &format!("    include_str!(\"example{example}.txt\"),\n");
+    }
+    module + "];"
+}
+
+/// Set the expected answer of `part` of `example` of `day` in the source of `days.rs`, adding the
+/// example if it is missing and replacing an earlier expected answer of the part.
+pub fn set_expected(
+    days: &str,
+    day: u8,
+    example: usize,
+    part: u8,
+    expected: &str,
+) -> Result<String> {
+    let mut lines: Vec<String> = days.lines().map(String::from).collect();
+    let day_start = lines
+        .iter()
+        .position(|line| line.trim() == format!("Day {day:02} {{"))
+        .with_context(|| format!("Day {day:02} is not found in days.rs"))?;
+    let day_end = day_start
+        + lines[day_start..]
+            .iter()
+            .position(|line| line == "    }")
+            .context("Day is not closed in days.rs")?;
+
+    let entry = format!("            part {part} expected {expected:?},");
+    let header = format!("        example {example} {{");
+    match lines[day_start..day_end]
+        .iter()
+        .position(|line| *line == header)
+    {
+        Some(offset) => {
+            let start = day_start + offset + 1;
+            let end = start
+                + lines[start..day_end]
+                    .iter()
+                    .position(|line| line == "        }")
+                    .context("Example is not closed in days.rs")?;
+            let prefix = format!("            part {part} ");
+            if let Some(line) = lines[start..end]
+                .iter_mut()
+                .find(|line| line.starts_with(&prefix))
+            {
+                *line = entry;
+            } else {
+                let before = lines[start..end]
+                    .iter()
+                    .position(|line| line.as_str() > prefix.as_str())
+                    .map_or(end, |index| start + index);
+                lines.insert(before, entry);
+            }
+        }
+        None => {
+            // Before the first later example, or the bench settings
+            let before = (day_start + 1..day_end)
+                .find(|index| {
+                    let line = &lines[*index];
+                    line == "        bench {"
+                        || line
+                            .trim()
+                            .strip_prefix("example ")
+                            .and_then(|rest| rest.trim_end_matches(" {").parse::<usize>().ok())
+                            .is_some_and(|other| other > example)
+                })
+                .unwrap_or(day_end);
+            lines.splice(before..before, [header, entry, "        }".to_string()]);
+        }
+    }
+
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 7: Camel Cards ---</h2><p>For example:</p>
+<pre><code>32T3K 765
+T55J5 684
+</code></pre>
+<p>So, the first step is to put the hands in order of <em>strength</em>: <code>32T3K</code> is the weakest.</p>
+<pre><code>A &lt; K &amp; <em>Q</em>
+</code></pre>
+<p>So the total winnings in this example are <code><em>6440</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>123</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>In the example above, the new total winnings are <code><em>5905</em></code>.</p>
+</article>
+</main>"#;
+
+#[cfg(test)]
+#[test]
+fn examples_and_answers_are_extracted() {
+    let puzzle = Puzzle::parse(PAGE).unwrap();
+
+    assert_eq!(puzzle.day, Some(7));
+    assert_eq!(puzzle.blocks(), ["32T3K 765\nT55J5 684", "A < K & Q"]);
+    assert_eq!(puzzle.articles[0].answers, ["6440"]);
+    assert_eq!(
+        puzzle.suggest(),
+        [
+            Assignment {
+                part: 1,
+                block: 1,
+                expected: "6440".to_string()
+            },
+            Assignment {
+                part: 2,
+                block: 1,
+                expected: "5905".to_string()
+            },
+        ]
+    );
+    assert!(Puzzle::parse("<p>No examples</p>").is_err());
+}
+
+#[cfg(test)]
+#[test]
+fn expected_answers_are_set_in_days() {
+    let days = "crate::days! {\n    Day 07 {\n        example 2 {\n            part 2 expected \"1\",\n        }\n        bench {\n            sample size 10\n        }\n    }\n}\n";
+
+    let days = set_expected(days, 7, 2, 1, "6440").unwrap();
+    let days = set_expected(&days, 7, 2, 2, "5905").unwrap();
+    let days = set_expected(&days, 7, 1, 1, "1").unwrap();
+    let days = set_expected(&days, 7, 3, 2, "\"quoted\"").unwrap();
+    assert_eq!(
+        days,
+        "crate::days! {
+    Day 07 {
+        example 1 {
+            part 1 expected \"1\",
+        }
+        example 2 {
+            part 1 expected \"6440\",
+            part 2 expected \"5905\",
+        }
+        example 3 {
+            part 2 expected \"\\\"quoted\\\"\",
+        }
+        bench {
+            sample size 10
+        }
+    }
+}
+"
+    );
+    assert!(set_expected(&days, 8, 1, 1, "1").is_err());
+
+    assert_eq!(
+        inputs_module(7, 1),
+        std::fs::read_to_string(crate::watch::day_dir(7).join("inputs").join("mod.rs")).unwrap()
+    );
+}
@@ -0,0 +1,249 @@
+//! Downloading puzzle inputs from Advent of Code, or a compatible server, for the `fetch`
+//! subcommand, and submitting answers for the `submit` subcommand.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{bail, Context, Result};
+
+pub const YEAR: u16 = 2023;
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+/// Sent with every request, so the maintainers of Advent of Code know where the traffic comes from
+/// and who to contact about it, as they ask of automated tools.
+pub const USER_AGENT: &str = concat!(
+    "aoc2023/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/markjansnl/aoc2023; contact: https://github.com/markjansnl/aoc2023/issues)"
+);
+
+/// Environment variable with the session cookie of a logged in user.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// Environment variable with the path of a file containing the session cookie.
+pub const SESSION_FILE_VAR: &str = "AOC_SESSION_FILE";
+
+/// The session cookie from `AOC_SESSION`, or else from `file`, see [`session_file`].
+pub fn session(file: Option<&Path>) -> Result<String> {
+    if let Ok(session) = env::var(SESSION_VAR) {
+        return Ok(session.trim().to_string());
+    }
+
+    let path =
+        file.with_context(|| format!("No session token, set {SESSION_VAR} or --session-file"))?;
+    let session = fs::read_to_string(path).with_context(|| {
+        format!(
+            "No session token, set {SESSION_VAR} or save the session cookie in {}",
+            path.display()
+        )
+    })?;
+    Ok(session.trim().to_string())
+}
+
+/// The file with the session cookie: the first of `file` (`--session-file`), the file in
+/// `AOC_SESSION_FILE`, `configured` (`session-file` of `aoc.toml`) and `~/.config/aoc/session`.
+pub fn session_file(file: Option<&Path>, configured: Option<&Path>) -> Option<PathBuf> {
+    file.map(Path::to_path_buf)
+        .or_else(|| env::var_os(SESSION_FILE_VAR).map(PathBuf::from))
+        .or_else(|| configured.map(Path::to_path_buf))
+        .or_else(|| {
+            env::var_os("HOME")
+                .map(|home| Path::new(&home).join(".config").join("aoc").join("session"))
+        })
+}
+
+/// Downloads inputs and submits answers. A copy of every download is kept, so each input is
+/// requested only once, and requests wait at least `rate_limit` after the previous one, also across
+/// runs.
+pub struct Fetcher {
+    pub base_url: String,
+    pub session: String,
+    pub cache_dir: PathBuf,
+    pub rate_limit: Duration,
+}
+
+impl Fetcher {
+    /// Default location of downloaded inputs: next to the executable. They only keep an input from
+    /// being requested twice, so it doesn't matter that `cargo clean` removes them.
+    pub fn default_cache_dir() -> Result<PathBuf> {
+        Ok(env::current_exe()?.with_file_name("aoc2023-downloads"))
+    }
+
+    /// The input of a day, downloaded unless it was downloaded from the same server before.
+    pub fn input(&self, day: u8) -> Result<String> {
+        check_day(day)?;
+        let cached = self.cached_path(day);
+        if let Ok(input) = fs::read_to_string(&cached) {
+            return Ok(input);
+        }
+
+        let url = format!(
+            "{}/{YEAR}/day/{day}/input",
+            self.base_url.trim_end_matches('/')
+        );
+        let input = self
+            .get(&url)
+            .with_context(|| format!("Cannot fetch input of day {day}"))?;
+
+        fs::create_dir_all(cached.parent().unwrap())?;
+        fs::write(&cached, &input)?;
+        Ok(input)
+    }
+
+    /// Submit `answer` for part `level` of a day, returning the page with the verdict.
+    pub fn answer(&self, day: u8, level: u8, answer: &str) -> Result<String> {
+        check_day(day)?;
+        let url = format!(
+            "{}/{YEAR}/day/{day}/answer",
+            self.base_url.trim_end_matches('/')
+        );
+        self.wait()?;
+        let response = self
+            .request("POST", &url)
+            .send_form(&[("level", &level.to_string()), ("answer", answer)]);
+        Self::body(response).with_context(|| format!("Cannot submit answer of day {day}"))
+    }
+
+    fn get(&self, url: &str) -> Result<String> {
+        self.wait()?;
+        Self::body(self.request("GET", url).call())
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        ureq::request(method, url)
+            .set("User-Agent", USER_AGENT)
+            .set("Cookie", &format!("session={}", self.session))
+    }
+
+    fn body(response: Result<ureq::Response, ureq::Error>) -> Result<String> {
+        match response {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(404, _)) => bail!("Not found, is the puzzle unlocked yet?"),
+            Err(ureq::Error::Status(400 | 500, _)) => {
+                bail!("Request refused, is the session token valid?")
+            }
+            Err(error) => Err(error)?,
+        }
+    }
+
+    /// Wait until `rate_limit` has passed since the previous request.
+    fn wait(&self) -> Result<()> {
+        let stamp = self.cache_dir.join("last-request");
+        if let Ok(elapsed) = fs::metadata(&stamp)
+            .and_then(|metadata| metadata.modified())
+            .map(|modified| {
+                SystemTime::now()
+                    .duration_since(modified)
+                    .unwrap_or_default()
+            })
+        {
+            thread::sleep(self.rate_limit.saturating_sub(elapsed));
+        }
+
+        fs::create_dir_all(&self.cache_dir)?;
+        fs::write(stamp, [])?;
+        Ok(())
+    }
+
+    /// Downloads of different servers are kept apart.
+    fn cached_path(&self, day: u8) -> PathBuf {
+        self.cache_dir
+            .join(server_dir(&self.base_url))
+            .join(format!("{YEAR}-day{day:02}.txt"))
+    }
+}
+
+/// Fail for days without a puzzle, before a rate limited request is spent on them.
+fn check_day(day: u8) -> Result<()> {
+    if !(1..=25).contains(&day) {
+        bail!("There is no day {day}, puzzles are released on days 1 to 25");
+    }
+    Ok(())
+}
+
+/// Name of the directory with the files of the server at `base_url`, e.g. `adventofcode.com`.
+pub fn server_dir(base_url: &str) -> String {
+    base_url
+        .split("://")
+        .last()
+        .unwrap_or_default()
+        .replace(|c: char| !c.is_ascii_alphanumeric() && c != '.', "_")
+}
+
+/// A server answering the requests in turn with `responses`, passing the requests it received on.
+#[cfg(test)]
+pub(crate) fn mock_server(responses: Vec<String>) -> (String, std::sync::mpsc::Receiver<String>) {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+    };
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        for response in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                request += &line;
+                if line.trim().is_empty() {
+                    break;
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            request += &String::from_utf8_lossy(&body);
+
+            let _ = sender.send(request);
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+
+    (base_url, receiver)
+}
+
+#[cfg(test)]
+#[test]
+fn fetches_once_with_session_and_user_agent() {
+    let (base_url, requests) = mock_server(vec![
+        "HTTP/1.1 200 OK\r\nContent-Length: 6\r\nConnection: close\r\n\r\n1\n2\n3\n".to_string(),
+        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+    ]);
+    let cache_dir = tempfile::tempdir().unwrap();
+    let fetcher = Fetcher {
+        base_url,
+        session: "secret".to_string(),
+        cache_dir: cache_dir.path().to_path_buf(),
+        rate_limit: Duration::ZERO,
+    };
+
+    assert_eq!(fetcher.input(1).unwrap(), "1\n2\n3\n");
+    let request = requests.recv().unwrap();
+    assert!(request.starts_with("GET /2023/day/1/input "), "{request}");
+    assert!(request.contains("Cookie: session=secret"), "{request}");
+    assert!(request.contains(USER_AGENT), "{request}");
+
+    // Cached, so the server isn't asked again
+    assert_eq!(fetcher.input(1).unwrap(), "1\n2\n3\n");
+
+    // Days without a puzzle don't send a request
+    assert!(fetcher.input(0).is_err());
+    assert!(fetcher.answer(26, 1, "1").is_err());
+
+    let error = fetcher.input(2).unwrap_err();
+    assert!(format!("{error:#}").contains("unlocked"), "{error:#}");
+    requests.recv().unwrap();
+    assert!(requests.try_recv().is_err());
+}
@@ -0,0 +1,69 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Part;
+
+/// Stable hash of a puzzle input. What [`normalize`](crate::normalize::normalize) removes is
+/// ignored: a byte order mark, the `\r` of `\r\n` line endings and trailing newlines. So the same
+/// input saved by another editor has the same fingerprint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Fingerprint(pub u64);
+
+impl Fingerprint {
+    /// 64 bit FNV-1a hash of the input. This is a `const fn`, so days can pin a part to an
+    /// input at compile time.
+    pub const fn of(input: &str) -> Self {
+        let bytes = input.as_bytes();
+
+        let mut end = bytes.len();
+        while end > 0 && bytes[end - 1] == b'\n' {
+            end -= 1;
+            while end > 0 && bytes[end - 1] == b'\r' {
+                end -= 1;
+            }
+        }
+
+        let mut hash: u64 = 0xcbf29ce484222325;
+        let mut i = 0;
+        if end >= 3 && bytes[0] == 0xef && bytes[1] == 0xbb && bytes[2] == 0xbf {
+            i = 3;
+        }
+        while i < end {
+            // Skip the run of `\r` of a line ending
+            let mut next = i;
+            while next < end && bytes[next] == b'\r' {
+                next += 1;
+            }
+            if next > i && next < end && bytes[next] == b'\n' {
+                i = next;
+            }
+            hash ^= bytes[i] as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+            i += 1;
+        }
+
+        Self(hash)
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+/// A part that only gives the right answer for specific inputs, e.g. because (part of) the answer
+/// was found by hand and is hard-coded.
+pub struct InputSpecific {
+    pub part: Part,
+    pub fingerprints: &'static [Fingerprint],
+    pub reason: &'static str,
+}
+
+#[cfg(test)]
+#[test]
+fn fingerprint_ignores_line_endings() {
+    assert_eq!(Fingerprint::of("a\nb\n"), Fingerprint::of("a\r\nb\r\n\r\n"));
+    assert_ne!(Fingerprint::of("a\nb"), Fingerprint::of("a\nc"));
+}
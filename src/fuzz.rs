@@ -0,0 +1,101 @@
+//! Robustness tests: whatever the input, parsing it and running the parts gives an answer or an
+//! error, but never panics.
+//!
+//! Each part runs on a worker thread with a timeout. A mutated input can make a solution run very
+//! long (e.g. by adding digits to a number), which is not considered a failure.
+
+use std::time::Duration;
+
+use fastrand::Rng;
+
+use crate::{cancel::run_day_part_with_timeout, error::Error, get_input, prelude::*, property};
+
+const CASES: usize = 20;
+const TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Characters that are likely to get further into a parser than arbitrary bytes.
+const CHARACTERS: &[char] = &[
+    '0', '1', '2', '5', '9', '-', '+', ' ', ',', ':', ';', '=', '.', '#', '\n', '\n', '(', ')',
+    '{', '}', '<', '>', '|', '/', '\\', 'a', 'z', 'A', 'Z', 'S', 'L', 'R', 'U', 'D', 'O', '%', '&',
+    '?', '~', '@',
+];
+
+fn run(day: u8, part: Part, input: &str) -> Result<()> {
+    let input = property::leak(input.to_string());
+    match run_day_part_with_timeout(day, part, input, TIMEOUT) {
+        Ok(_) => Ok(()),
+        Err(error) => match error.downcast_ref::<Error>() {
+            Some(Error::WorkerPanicked(_)) => Err(error),
+            _ => Ok(()),
+        },
+    }
+}
+
+fn arbitrary(rng: &mut Rng, size: usize) -> String {
+    let bytes = (0..rng.usize(0..=size * 8))
+        .map(|_| match rng.u8(0..4) {
+            0 => rng.u8(..),
+            _ => *rng.choice(CHARACTERS).unwrap() as u8,
+        })
+        .collect::<Vec<_>>();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn mutate(rng: &mut Rng, example: &str, mutations: usize) -> String {
+    let mut chars = example.chars().collect::<Vec<_>>();
+    for _ in 0..mutations {
+        let i = rng.usize(0..=chars.len());
+        match rng.u8(0..6) {
+            0 if i < chars.len() => chars[i] = *rng.choice(CHARACTERS).unwrap(),
+            1 if i < chars.len() => {
+                chars.drain(i..rng.usize(i..=chars.len().min(i + 8)));
+            }
+            2 => chars.insert(i, *rng.choice(CHARACTERS).unwrap()),
+            3 => chars.truncate(i),
+            4 => {
+                // Duplicate a line, or a part of one
+                let end = chars[i..]
+                    .iter()
+                    .position(|c| *c == '\n')
+                    .map_or(chars.len(), |end| i + end + 1);
+                let line = chars[i..end].to_vec();
+                chars.splice(i..i, line);
+            }
+            _ if i < chars.len() && chars[i].is_ascii_digit() => {
+                chars.splice(i..i, (0..rng.usize(1..=4)).map(|_| rng.digit(10)));
+            }
+            _ => {}
+        }
+    }
+    chars.into_iter().collect()
+}
+
+fn check_day(day: &def::Day, generate: impl Fn(&mut Rng, usize) -> String) {
+    for part in [Part1, Part2] {
+        property::check_cases(CASES, 8, &generate, |input| {
+            run(day.day, part, input).with_context(|| format!("Day {} {part:?} panicked", day.day))
+        });
+    }
+}
+
+#[test]
+fn arbitrary_input_never_panics() {
+    for day in DAYS {
+        check_day(day, arbitrary);
+    }
+}
+
+#[test]
+fn mutated_examples_never_panic() {
+    for day in DAYS.iter().filter(|day| !day.examples.is_empty()) {
+        let examples = day
+            .examples
+            .iter()
+            .map(|example| get_input(day.day, example.example).unwrap())
+            .collect::<Vec<_>>();
+        check_day(day, |rng, size| {
+            let example = examples[rng.usize(0..examples.len())];
+            mutate(rng, example, size)
+        });
+    }
+}
@@ -0,0 +1,254 @@
+//! Persistent history of the answers and timings of every run, and the trends of the `history`
+//! subcommand: how the time of a part evolved, and when its answer changed.
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{config, fetch::YEAR, fingerprint::Fingerprint, Outcome};
+
+/// The commit the running executable was built from, if it was built in a git repository.
+pub fn commit() -> Option<&'static str> {
+    Some(env!("AOC_GIT_COMMIT")).filter(|commit| !commit.is_empty())
+}
+
+/// A part run on an input.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+    /// RFC 3339 time of the run
+    pub time: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    pub day: u8,
+    pub part: u8,
+    pub input: Fingerprint,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+    pub outcome: Outcome,
+    pub elapsed_ms: f64,
+}
+
+/// History file with a run per line, as JSON.
+pub struct History {
+    path: PathBuf,
+}
+
+impl History {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Default location of the history, in the data directory: the trends span many builds, so
+    /// the history has to survive `cargo clean` and be shared by the debug and release builds.
+    pub fn default_path() -> Result<PathBuf> {
+        let dir = config::data_dir().context("Cannot find the home directory")?;
+        Ok(dir.join(format!("{YEAR}-history.jsonl")))
+    }
+
+    /// Append a run to the history.
+    pub fn record(&self, run: &Run) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(run)?)?;
+        Ok(())
+    }
+
+    /// All runs, oldest first, and the numbers of the lines that aren't a run, like a line cut
+    /// off by an interrupted write. A missing history has no runs.
+    pub fn runs(&self) -> (Vec<Run>, Vec<usize>) {
+        let Ok(history) = fs::read_to_string(&self.path) else {
+            return (Vec::new(), Vec::new());
+        };
+        let mut runs = Vec::new();
+        let mut invalid = Vec::new();
+        for (index, line) in history.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(line) {
+                Ok(run) => runs.push(run),
+                Err(_) => invalid.push(index + 1),
+            }
+        }
+        (runs, invalid)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// The runs of a part of a day on one input, with one solver variant.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Trend {
+    pub day: u8,
+    pub part: u8,
+    pub input: Fingerprint,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+    pub runs: usize,
+    /// Time of the first, fastest and last run with an answer
+    pub first_ms: Option<f64>,
+    pub best_ms: Option<f64>,
+    pub last_ms: Option<f64>,
+    /// The outcome of the last run
+    pub last: Outcome,
+    pub changes: Vec<Change>,
+    /// Times of the runs with an answer, oldest first
+    #[serde(skip)]
+    pub timings: Vec<f64>,
+}
+
+/// A run with a different answer than the run before it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Change {
+    pub time: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    pub from: String,
+    pub to: String,
+}
+
+/// The trend of every part, input and variant in `runs`, by day, part, input and variant. Only
+/// answers count as changes; a failing run doesn't change the answer.
+pub fn trends(runs: &[Run]) -> Vec<Trend> {
+    let mut trends = BTreeMap::<(u8, u8, u64, Option<&str>), (Trend, Option<&str>)>::new();
+    for run in runs {
+        let (trend, last_answer) = trends
+            .entry((run.day, run.part, run.input.0, run.variant.as_deref()))
+            .or_insert_with(|| {
+                let trend = Trend {
+                    day: run.day,
+                    part: run.part,
+                    input: run.input,
+                    variant: run.variant.clone(),
+                    runs: 0,
+                    first_ms: None,
+                    best_ms: None,
+                    last_ms: None,
+                    last: run.outcome.clone(),
+                    changes: Vec::new(),
+                    timings: Vec::new(),
+                };
+                (trend, None)
+            });
+        trend.runs += 1;
+        trend.last = run.outcome.clone();
+
+        let Outcome::Answer { output } = &run.outcome else {
+            continue;
+        };
+        if let Some(previous) = last_answer.filter(|previous| previous != output) {
+            trend.changes.push(Change {
+                time: run.time.clone(),
+                commit: run.commit.clone(),
+                from: previous.to_string(),
+                to: output.clone(),
+            });
+        }
+        *last_answer = Some(output);
+
+        trend.first_ms = trend.first_ms.or(Some(run.elapsed_ms));
+        trend.best_ms = Some(
+            trend
+                .best_ms
+                .map_or(run.elapsed_ms, |best| best.min(run.elapsed_ms)),
+        );
+        trend.last_ms = Some(run.elapsed_ms);
+        trend.timings.push(run.elapsed_ms);
+    }
+    trends.into_values().map(|(trend, _)| trend).collect()
+}
+
+/// A line of bars showing the relative sizes of `values`, e.g. `▂▂▁█`.
+pub fn sparkline(values: &[f64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = values.iter().copied().fold(0.0, f64::max);
+    values
+        .iter()
+        .map(|value| match max > 0.0 {
+            true => BARS[((value / max) * 7.0).round() as usize],
+            false => BARS[0],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[test]
+fn trends_flag_answer_changes() {
+    let run = |time: &str, part: u8, outcome: Outcome, elapsed_ms: f64| Run {
+        time: time.to_string(),
+        commit: Some(format!("commit-{time}")),
+        day: 7,
+        part,
+        input: Fingerprint(1),
+        variant: None,
+        outcome,
+        elapsed_ms,
+    };
+    let answer = |output: &str| Outcome::Answer {
+        output: output.to_string(),
+    };
+    let error = Outcome::Error {
+        message: "parse error".to_string(),
+    };
+
+    let dir = tempfile::tempdir().unwrap();
+    let history = History::new(dir.path().join("aoc").join("2023-history.jsonl"));
+    for run in [
+        run("1", 1, answer("10"), 8.0),
+        run("2", 1, error.clone(), 1.0),
+        run("3", 1, answer("10"), 4.0),
+        run("4", 1, answer("12"), 2.0),
+        run("5", 2, answer("1"), 1.0),
+        run("6", 1, Outcome::Timeout { timeout_ms: 1000 }, 1000.0),
+        Run {
+            variant: Some("brute-force".to_string()),
+            ..run("7", 1, answer("11"), 50.0)
+        },
+    ] {
+        history.record(&run).unwrap();
+    }
+    let mut file = OpenOptions::new()
+        .append(true)
+        .open(history.path())
+        .unwrap();
+    write!(file, "{{\"time\":\"8\",\"day\":7,").unwrap();
+    let (runs, invalid) = history.runs();
+    assert_eq!(runs.len(), 7);
+    assert_eq!(invalid, [8]);
+
+    let trends = trends(&runs);
+    assert_eq!(trends.len(), 3);
+    let part1 = &trends[0];
+    assert_eq!(part1.runs, 5);
+    assert_eq!(
+        (part1.first_ms, part1.best_ms, part1.last_ms),
+        (Some(8.0), Some(2.0), Some(2.0))
+    );
+    assert_eq!(part1.last, Outcome::Timeout { timeout_ms: 1000 });
+    assert_eq!(
+        part1.changes,
+        [Change {
+            time: "4".to_string(),
+            commit: Some("commit-4".to_string()),
+            from: "10".to_string(),
+            to: "12".to_string(),
+        }]
+    );
+    assert_eq!(sparkline(&part1.timings), "█▅▃");
+    assert_eq!(trends[1].variant.as_deref(), Some("brute-force"));
+    assert!(trends[1].changes.is_empty());
+    assert!(trends[2].changes.is_empty());
+}
@@ -0,0 +1,91 @@
+use std::{
+    io,
+    os::unix::process::{CommandExt, ExitStatusExt},
+    process::{Command, Stdio},
+};
+
+use crate::{Outcome, Result};
+
+/// Resource limits for an isolated child process.
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    /// Maximum address space in MiB.
+    pub memory_mib: u64,
+    /// Maximum CPU time in seconds.
+    pub cpu_seconds: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            memory_mib: 1024,
+            cpu_seconds: 60,
+        }
+    }
+}
+
+/// Run `command` as a child process with `limits` applied. The child is expected to write a single
+/// JSON serialized [`Outcome`] to stdout. If it dies instead, the exit status and stderr are used to
+/// tell running out of memory, running out of CPU time and other crashes apart.
+pub fn run_isolated(mut command: Command, limits: Limits) -> Result<Outcome> {
+    // SAFETY: the closure runs in the forked child before `exec`, where only async-signal-safe
+    // functions may be called. It only calls `setrlimit`, which is a plain system call, and
+    // `io::Error::last_os_error`, which reads `errno`; it doesn't allocate or take locks.
+    unsafe {
+        command.pre_exec(move || {
+            let set_limit = |resource, soft, hard| {
+                let limit = libc::rlimit {
+                    rlim_cur: soft,
+                    rlim_max: hard,
+                };
+                if libc::setrlimit(resource, &limit) == 0 {
+                    Ok(())
+                } else {
+                    Err(io::Error::last_os_error())
+                }
+            };
+
+            let memory = limits.memory_mib.saturating_mul(1024 * 1024);
+            set_limit(libc::RLIMIT_AS, memory, memory)?;
+            // The soft limit sends SIGXCPU, one second later the hard limit sends SIGKILL
+            let cpu = limits.cpu_seconds;
+            set_limit(libc::RLIMIT_CPU, cpu, cpu.saturating_add(1))
+        });
+    }
+
+    let output = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if output.status.success() {
+        if let Ok(outcome) = serde_json::from_str(&stdout) {
+            return Ok(outcome);
+        }
+    }
+
+    // A SIGKILL may come from the OOM killer or from outside as well, so only SIGXCPU is a timeout
+    let signal = output.status.signal();
+    Ok(if signal == Some(libc::SIGXCPU) {
+        Outcome::Timeout {
+            timeout_ms: limits.cpu_seconds.saturating_mul(1000),
+        }
+    } else if stderr.contains("memory allocation of") {
+        Outcome::OutOfMemory {
+            limit_mib: limits.memory_mib,
+        }
+    } else {
+        Outcome::Crashed {
+            signal,
+            message: stderr
+                .lines()
+                .rfind(|line| !line.trim().is_empty())
+                .unwrap_or("No output")
+                .to_string(),
+        }
+    })
+}
@@ -2,8 +2,34 @@
 
 use anyhow::Result;
 
+pub mod batch;
+pub mod cache;
+pub mod cancel;
+pub mod config;
 pub mod def;
+pub mod encrypt;
+pub mod extract;
+pub mod fetch;
+pub mod fingerprint;
+#[cfg(test)]
+mod fuzz;
+pub mod history;
+#[cfg(target_os = "linux")]
+pub mod isolate;
+pub mod normalize;
+pub mod observe;
+pub mod parse;
 pub mod prelude;
+#[cfg(test)]
+mod property;
+pub mod render;
+pub mod scale;
+pub mod submit;
+pub mod trace;
+pub mod validate;
+pub mod variant;
+pub mod visualize;
+pub mod watch;
 
 mod days;
 mod error;
@@ -11,9 +37,22 @@ mod error;
 use Part::*;
 
 use criterion::{measurement::WallTime, BenchmarkGroup};
-pub use days::{bench_day, get_input, reuse_parsed, run_day};
+pub use days::{
+    bench_day, dump_parsed, generate_input, get_input, observe_day, render_day, reuse_parsed,
+    run_day, run_day_timed, run_day_variant, validate_day, variants, visualize_day,
+};
+use error::{Error, ParseError};
+use fingerprint::{Fingerprint, InputSpecific};
+use normalize::normalize;
+use observe::Observer;
+use render::{Image, Render};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use validate::Violation;
+use variant::Variant;
+use visualize::Visualize;
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Part {
     Part1,
     Part2,
@@ -29,6 +68,15 @@ impl From<u8> for Part {
     }
 }
 
+impl From<Part> for u8 {
+    fn from(part: Part) -> u8 {
+        match part {
+            Part1 => 1,
+            Part2 => 2,
+        }
+    }
+}
+
 pub enum Reuse<T> {
     SingleUse(T),
     Reusable(T),
@@ -36,14 +84,53 @@ pub enum Reuse<T> {
 
 pub trait Day {
     const INPUTS: Self::Inputs;
+    /// Parts that don't work for any input. Running them on another input fails with an error.
+    const INPUT_SPECIFIC: &'static [InputSpecific] = &[];
     type Inputs;
-    type Parsed;
-    type Output: ToString;
+    /// Serializable, so the parsed input can be dumped as JSON with `--dump-parsed`.
+    type Parsed: Serialize + 'static;
+    type Output: ToString + 'static;
+    /// Alternative solutions of the parts, next to the default `part1` and `part2`. Parts that only
+    /// work for specific inputs refer to the default solution.
+    const VARIANTS: &'static [Variant<Self::Parsed, Self::Output>] = &[];
 
     fn reuse_parsed() -> bool;
     fn parse(input: &'static str, part: Part) -> Result<Self::Parsed>;
     fn part1(parsed: &Self::Parsed) -> Result<Self::Output>;
     fn part2(parsed: &Self::Parsed) -> Result<Self::Output>;
+
+    /// Check the assumptions about the input the solution relies on.
+    fn validate(_parsed: &Self::Parsed) -> Vec<Violation> {
+        Vec::new()
+    }
+
+    /// Generate a random, valid puzzle input. What `size` means depends on the day, e.g. the width
+    /// of a grid or the number of lines. Days without a generator return `None`.
+    fn generate(_rng: &mut fastrand::Rng, _size: usize) -> Option<String> {
+        None
+    }
+
+    /// The parsed or solved state of a part to show with `--visualize`. Days without a
+    /// visualization return `None`.
+    fn visualize(_parsed: &Self::Parsed, _part: Part) -> Result<Option<Box<dyn Visualize + '_>>> {
+        Ok(None)
+    }
+
+    /// The parsed or solved state of a part to draw with `--render`. Days without an image
+    /// return `None`.
+    fn render(_parsed: &Self::Parsed, _part: Part) -> Result<Option<Box<dyn Render + '_>>> {
+        Ok(None)
+    }
+
+    /// Solve a part while sending the steps of its simulation to `observer`. Parts that aren't
+    /// simulated step by step return `None`.
+    fn observe(
+        _parsed: &Self::Parsed,
+        _part: Part,
+        _observer: &mut dyn Observer,
+    ) -> Result<Option<Self::Output>> {
+        Ok(None)
+    }
 }
 
 fn run_day_generic<D: Day>(
@@ -51,31 +138,156 @@ fn run_day_generic<D: Day>(
     part1: bool,
     part2: bool,
 ) -> Result<(Option<String>, Option<String>)> {
+    let (output1, output2) = run_day_timed_generic::<D>(input, part1, part2)?;
+    Ok((
+        output1.map(|(o, _)| o).transpose()?,
+        output2.map(|(o, _)| o).transpose()?,
+    ))
+}
+
+/// The answers or errors of the parts of a day, with the time each part took.
+pub type Timed = (
+    Option<(Result<String>, Duration)>,
+    Option<(Result<String>, Duration)>,
+);
+
+/// Like [`run_day_generic`], also returning how long each part took, and the error of the part that
+/// failed. Part 2 doesn't run once part 1 failed. The time of a part includes parsing, unless part 2
+/// reuses the parsed input of part 1.
+fn run_day_timed_generic<D: Day>(input: &'static str, part1: bool, part2: bool) -> Result<Timed> {
+    let input = normalize(input);
     let mut parsed_part1 = None;
     let mut output = (None, None);
 
     if part1 {
-        let parsed = D::parse(input, Part1)?;
-        output.0 = Some(D::part1(&parsed)?.to_string());
-        parsed_part1 = Some(parsed);
+        output.0 = Some(timed(|| {
+            check_input_specific::<D>(input, Part1)?;
+            let parsed = parse::<D>(input, Part1)?;
+            let _span = tracing::info_span!("part1").entered();
+            let answer = D::part1(&parsed)?.to_string();
+            parsed_part1 = Some(parsed);
+            Ok(answer)
+        }));
+        if parsed_part1.is_none() {
+            return Ok(output);
+        }
     }
 
     if part2 {
-        let parsed = if part1 && D::reuse_parsed() {
-            parsed_part1.unwrap()
-        } else {
-            D::parse(input, Part2)?
-        };
-        output.1 = Some(D::part2(&parsed)?.to_string());
+        output.1 = Some(timed(|| {
+            check_input_specific::<D>(input, Part2)?;
+            let parsed = match parsed_part1.filter(|_| D::reuse_parsed()) {
+                Some(parsed) => parsed,
+                None => parse::<D>(input, Part2)?,
+            };
+            let _span = tracing::info_span!("part2").entered();
+            Ok(D::part2(&parsed)?.to_string())
+        }));
     }
 
     Ok(output)
 }
 
+/// Run a part, with the time it took.
+fn timed(part: impl FnOnce() -> Result<String>) -> (Result<String>, Duration) {
+    let start = Instant::now();
+    (part(), start.elapsed())
+}
+
+/// Run a single part with the named solver variant. Parts without the variant run the default
+/// solution, but the variant must exist for one of the parts.
+fn run_day_variant_generic<D: Day>(
+    input: &'static str,
+    part: Part,
+    variant: &str,
+) -> Result<String> {
+    if variant != variant::DEFAULT {
+        if let Some(variant) = D::VARIANTS
+            .iter()
+            .find(|v| v.part == part && v.name == variant)
+        {
+            let parsed = parse::<D>(normalize(input), part)?;
+            let _span =
+                tracing::info_span!("variant", part = u8::from(part), variant = variant.name)
+                    .entered();
+            return Ok((variant.solve)(&parsed)?.to_string());
+        } else if !D::VARIANTS.iter().any(|v| v.name == variant) {
+            Err(Error::VariantNotFound(variant.to_string()))?
+        }
+    }
+
+    let (part1, part2) = run_day_generic::<D>(input, part == Part1, part == Part2)?;
+    Ok(part1.or(part2).unwrap())
+}
+
+fn validate_day_generic<D: Day>(input: &'static str) -> Result<Vec<Violation>> {
+    let input = normalize(input);
+    let mut violations = D::validate(&parse::<D>(input, Part1)?);
+
+    if !D::reuse_parsed() {
+        for violation in D::validate(&parse::<D>(input, Part2)?) {
+            if !violations.contains(&violation) {
+                violations.push(violation);
+            }
+        }
+    }
+
+    Ok(violations)
+}
+
+fn dump_parsed_generic<D: Day>(input: &'static str, part: Part) -> Result<serde_json::Value> {
+    let parsed = parse::<D>(normalize(input), part)?;
+    Ok(serde_json::to_value(parsed)?)
+}
+
+fn visualize_day_generic<D: Day>(input: &'static str, part: Part) -> Result<Option<String>> {
+    let parsed = parse::<D>(normalize(input), part)?;
+    let visualization = D::visualize(&parsed, part)?;
+    Ok(visualization.map(|visualization| visualization.visualize()))
+}
+
+fn render_day_generic<D: Day>(input: &'static str, part: Part) -> Result<Option<Image>> {
+    let parsed = parse::<D>(normalize(input), part)?;
+    let render = D::render(&parsed, part)?;
+    Ok(render.map(|render| render.render()))
+}
+
+fn observe_day_generic<D: Day>(
+    input: &'static str,
+    part: Part,
+    observer: &mut dyn Observer,
+) -> Result<Option<String>> {
+    let input = normalize(input);
+    check_input_specific::<D>(input, part)?;
+    let parsed = parse::<D>(input, part)?;
+    Ok(D::observe(&parsed, part, observer)?.map(|output| output.to_string()))
+}
+
+/// Parse a normalized input, with the position of parse errors.
+fn parse<D: Day>(input: &'static str, part: Part) -> Result<D::Parsed> {
+    let _span = tracing::info_span!("parse", part = u8::from(part)).entered();
+    D::parse(input, part).map_err(|error| ParseError::locate(error, input))
+}
+
+fn check_input_specific<D: Day>(input: &str, part: Part) -> Result<()> {
+    let fingerprint = Fingerprint::of(input);
+    for input_specific in D::INPUT_SPECIFIC.iter().filter(|i| i.part == part) {
+        if !input_specific.fingerprints.contains(&fingerprint) {
+            Err(Error::InputSpecific {
+                part: part.into(),
+                reason: input_specific.reason,
+                fingerprint,
+            })?
+        }
+    }
+    Ok(())
+}
+
 fn bench_day_generic<D: Day>(
     input: &'static str,
     group: &mut BenchmarkGroup<'_, WallTime>,
 ) -> Result<()> {
+    let input = normalize(input);
     // group.sample_size(10);
 
     if D::reuse_parsed() {
@@ -92,6 +304,12 @@ fn bench_day_generic<D: Day>(
     let parsed = D::parse(input, Part2)?;
     group.bench_function("Part 2", |b| b.iter(|| D::part2(&parsed)));
 
+    for variant in D::VARIANTS {
+        let parsed = D::parse(input, variant.part)?;
+        let name = format!("Part {} {}", u8::from(variant.part), variant.name);
+        group.bench_function(name, |b| b.iter(|| (variant.solve)(&parsed)));
+    }
+
     Ok(())
 }
 
@@ -103,19 +321,130 @@ pub fn run_day_part(day: u8, part: Part, input: &'static str) -> Result<String>
     })
 }
 
+/// Result of running a single part, when the part may not finish.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Outcome {
+    Answer {
+        output: String,
+    },
+    Error {
+        message: String,
+    },
+    Timeout {
+        timeout_ms: u64,
+    },
+    OutOfMemory {
+        limit_mib: u64,
+    },
+    Crashed {
+        signal: Option<i32>,
+        message: String,
+    },
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Answer { output } => write!(f, "{output}"),
+            Outcome::Error { message } => write!(f, "Error: {message}"),
+            Outcome::Timeout { timeout_ms } => write!(f, "Timed out after {timeout_ms} ms"),
+            Outcome::OutOfMemory { limit_mib } => write!(f, "Out of memory ({limit_mib} MiB)"),
+            Outcome::Crashed {
+                signal: Some(signal),
+                message,
+            } => write!(f, "Crashed with signal {signal}: {message}"),
+            Outcome::Crashed {
+                signal: None,
+                message,
+            } => write!(f, "Crashed: {message}"),
+        }
+    }
+}
+
 pub fn run_input(day: u8, part: Part, index: usize) -> Result<String> {
     run_day_part(day, part, get_input(day, index)?)
 }
 
+/// Call `test` with every part of every example that has an expected answer, and panic with the
+/// day, example and part when it fails.
 #[cfg(test)]
-pub fn test_example(day: u8, part: Part, example: usize, expected: String) -> Result<()> {
-    assert_eq!(expected, run_day_part(day, part, get_input(day, example)?)?);
+pub(crate) fn for_each_example(mut test: impl FnMut(u8, &def::Part, &'static str) -> Result<()>) {
+    for day in days::DAYS {
+        for example in day.examples {
+            for part in example.parts.iter().filter(|part| !part.error) {
+                let input = get_input(day.day, example.example).unwrap();
+                if let Err(error) = test(day.day, part, input) {
+                    panic!(
+                        "Day {} example {} part {}: {error:#}",
+                        day.day,
+                        example.example,
+                        u8::from(part.part)
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub fn test_example(
+    day: u8,
+    part: Part,
+    example: usize,
+    expected: String,
+    error: bool,
+) -> Result<()> {
+    let output = run_day_part(day, part, get_input(day, example)?);
+    if error {
+        match output {
+            Ok(output) => panic!("Expected error \"{expected}\", got output {output}"),
+            Err(error) => assert!(
+                format!("{error:#}").contains(&expected),
+                "Expected error \"{expected}\", got error \"{error:#}\""
+            ),
+        }
+    } else {
+        assert_eq!(expected, output?);
+    }
     Ok(())
 }
 
+#[cfg(test)]
+#[test]
+fn parsed_examples_dump_as_json() {
+    for_each_example(|day, part, input| {
+        let parsed = dump_parsed(day, input, part.part)?;
+        anyhow::ensure!(!parsed.is_null(), "parsed input is null");
+        Ok(())
+    });
+
+    let histories = dump_parsed(9, get_input(9, 1).unwrap(), Part1).unwrap();
+    assert_eq!(
+        histories,
+        serde_json::json!([
+            [0, 3, 6, 9, 12, 15],
+            [1, 3, 6, 10, 15, 21],
+            [10, 13, 16, 21, 30, 45]
+        ])
+    );
+}
+
+/// Used by [`days!`] to tell an expected answer from an expected error.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! expected_error {
+    () => {
+        false
+    };
+    (error) => {
+        true
+    };
+}
+
 #[macro_export]
 macro_rules! days {
-    ($(Day $day:literal { $(example $example:literal { $(part $part:literal expected $expected:literal,)+ })* $(bench { sample size $bench_sample_size:literal })? })+) => {
+    ($(Day $day:literal { $(example $example:literal { $(part $part:literal expected $($error:ident)? $expected:literal,)+ })* $(bench { sample size $bench_sample_size:literal })? })+) => {
         paste::paste! {
             $(
                 mod [< day $day >];
@@ -134,6 +463,7 @@ macro_rules! days {
                                             $crate::def::Part {
                                                 part: $crate :: Part :: [< Part $part >],
                                                 expected: $expected,
+                                                error: $crate::expected_error!($($error)?),
                                             },
                                         )+
                                     ],
@@ -150,6 +480,7 @@ macro_rules! days {
                 part1: bool,
                 part2: bool,
             ) -> anyhow::Result<(Option<String>, Option<String>)> {
+                let _span = tracing::info_span!("day", day).entered();
                 match day {
                     $(
                         $day => super::run_day_generic::< [< day $day >] :: [< Day $day >] >(input, part1, part2),
@@ -158,6 +489,129 @@ macro_rules! days {
                 }
             }
 
+            pub fn run_day_timed(
+                day: u8,
+                input: &'static str,
+                part1: bool,
+                part2: bool,
+            ) -> anyhow::Result<$crate::Timed> {
+                let _span = tracing::info_span!("day", day).entered();
+                match day {
+                    $(
+                        $day => super::run_day_timed_generic::< [< day $day >] :: [< Day $day >] >(input, part1, part2),
+                    )+
+                    _ => return Err(anyhow::anyhow!(format!("Day {day} is not implemented"))),
+                }
+            }
+
+            pub fn run_day_variant(
+                day: u8,
+                part: $crate::Part,
+                variant: &str,
+                input: &'static str,
+            ) -> anyhow::Result<String> {
+                let _span = tracing::info_span!("day", day).entered();
+                match day {
+                    $(
+                        $day => super::run_day_variant_generic::< [< day $day >] :: [< Day $day >] >(input, part, variant),
+                    )+
+                    _ => return Err(anyhow::anyhow!(format!("Day {day} is not implemented"))),
+                }
+            }
+
+            /// The named solver variants of a day, besides the default solution.
+            pub fn variants(day: u8) -> anyhow::Result<Vec<($crate::Part, &'static str)>> {
+                use $crate::Day;
+                Ok(match day {
+                    $(
+                        $day => < [< day $day >] :: [< Day $day >] >::VARIANTS
+                            .iter()
+                            .map(|variant| (variant.part, variant.name))
+                            .collect(),
+                    )+
+                    _ => return Err(anyhow::anyhow!(format!("Day {day} is not implemented"))),
+                })
+            }
+
+            pub fn validate_day(
+                day: u8,
+                input: &'static str,
+            ) -> anyhow::Result<Vec<$crate::validate::Violation>> {
+                match day {
+                    $(
+                        $day => super::validate_day_generic::< [< day $day >] :: [< Day $day >] >(input),
+                    )+
+                    _ => return Err(anyhow::anyhow!(format!("Day {day} is not implemented"))),
+                }
+            }
+
+            pub fn dump_parsed(
+                day: u8,
+                input: &'static str,
+                part: $crate::Part,
+            ) -> anyhow::Result<serde_json::Value> {
+                match day {
+                    $(
+                        $day => super::dump_parsed_generic::< [< day $day >] :: [< Day $day >] >(input, part),
+                    )+
+                    _ => return Err(anyhow::anyhow!(format!("Day {day} is not implemented"))),
+                }
+            }
+
+            pub fn visualize_day(
+                day: u8,
+                input: &'static str,
+                part: $crate::Part,
+            ) -> anyhow::Result<Option<String>> {
+                match day {
+                    $(
+                        $day => super::visualize_day_generic::< [< day $day >] :: [< Day $day >] >(input, part),
+                    )+
+                    _ => return Err(anyhow::anyhow!(format!("Day {day} is not implemented"))),
+                }
+            }
+
+            pub fn render_day(
+                day: u8,
+                input: &'static str,
+                part: $crate::Part,
+            ) -> anyhow::Result<Option<$crate::render::Image>> {
+                match day {
+                    $(
+                        $day => super::render_day_generic::< [< day $day >] :: [< Day $day >] >(input, part),
+                    )+
+                    _ => return Err(anyhow::anyhow!(format!("Day {day} is not implemented"))),
+                }
+            }
+
+            pub fn observe_day(
+                day: u8,
+                input: &'static str,
+                part: $crate::Part,
+                observer: &mut dyn $crate::observe::Observer,
+            ) -> anyhow::Result<Option<String>> {
+                match day {
+                    $(
+                        $day => super::observe_day_generic::< [< day $day >] :: [< Day $day >] >(input, part, observer),
+                    )+
+                    _ => return Err(anyhow::anyhow!(format!("Day {day} is not implemented"))),
+                }
+            }
+
+            pub fn generate_input(
+                day: u8,
+                rng: &mut fastrand::Rng,
+                size: usize,
+            ) -> anyhow::Result<Option<String>> {
+                use $crate::Day;
+                Ok(match day {
+                    $(
+                        $day => < [< day $day >] :: [< Day $day >] >::generate(rng, size),
+                    )+
+                    _ => return Err(anyhow::anyhow!(format!("Day {day} is not implemented"))),
+                })
+            }
+
             pub fn reuse_parsed(day: u8) -> anyhow::Result<bool> {
                 use $crate::Day;
                 Ok(match day {
@@ -173,6 +627,12 @@ macro_rules! days {
                 index: usize,
             ) -> anyhow::Result<&'static str> {
                 use $crate::Day;
+                if index == 0 && $crate::encrypt::locked(day) {
+                    return Err($crate::Error::InputLocked(day).into());
+                }
+                if index == 0 && $crate::encrypt::missing(day) {
+                    return Err($crate::Error::InputMissing(day).into());
+                }
                 Ok(match day {
                     $(
                         $day => < [< day $day >] :: [< Day $day >] >::INPUTS[index],
@@ -190,7 +650,13 @@ macro_rules! days {
                                 $(
                                     #[test]
                                     fn [< part $part >] () -> anyhow::Result<()> {
-                                        $crate::test_example($day, $part.into(), $example, $expected.to_string())
+                                        $crate::test_example(
+                                            $day,
+                                            $part.into(),
+                                            $example,
+                                            $expected.to_string(),
+                                            $crate::expected_error!($($error)?),
+                                        )
                                     }
                                 )*
                             }
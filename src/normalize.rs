@@ -0,0 +1,64 @@
+/// Normalize an input before it is parsed, so inputs saved by any editor are accepted: a byte order
+/// mark is removed, `\r\n` line endings (or any other run of `\r` before a `\n`) become `\n` and
+/// trailing newlines are removed. Other whitespace is kept, it may be part of the puzzle. [`Fingerprint::of`] ignores exactly these
+/// differences.
+///
+/// Inputs that are normalized already are returned as is. Otherwise the normalized input is leaked,
+/// because days need a `&'static str`.
+///
+/// [`Fingerprint::of`]: crate::fingerprint::Fingerprint::of
+pub fn normalize(input: &'static str) -> &'static str {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+
+    let normalized = input
+        .split_inclusive('\n')
+        .flat_map(|line| match line.strip_suffix('\n') {
+            Some(line) => [line.trim_end_matches('\r'), "\n"],
+            None => [line, ""],
+        })
+        .collect::<String>();
+    let normalized = normalized.trim_end_matches('\n');
+
+    if normalized == input {
+        input
+    } else {
+        normalized.to_string().leak()
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn normalize_line_endings_and_trailing_newlines() {
+    assert_eq!(normalize("\u{feff}ab\r\ncd  \r\n\r\n\n"), "ab\ncd  ");
+    assert_eq!(normalize("ab\n\ncd"), "ab\n\ncd");
+    assert_eq!(normalize("ab\rcd\r\r\nef\r"), "ab\rcd\nef\r");
+}
+
+#[cfg(test)]
+#[test]
+fn fingerprint_ignores_what_normalize_removes() {
+    use crate::fingerprint::Fingerprint;
+
+    let inputs = [
+        "ab\ncd",
+        "\u{feff}ab\r\ncd\r\n\n",
+        "ab\ncd  ",
+        "ab \ncd",
+        "ab\rcd",
+        "ab\r\rcd\r",
+        "ab\r\r\ncd\r\r\n",
+        "\n\nab\ncd",
+        "",
+        "\r\n",
+    ];
+    for a in inputs {
+        assert_eq!(Fingerprint::of(a), Fingerprint::of(normalize(a)), "{a:?}");
+        for b in inputs {
+            assert_eq!(
+                Fingerprint::of(a) == Fingerprint::of(b),
+                normalize(a) == normalize(b),
+                "{a:?} and {b:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! Observers of the days that simulate step by step, e.g. the spin cycles of day 14 or the pulses
+//! of day 20. Run a part with [`observe_day`](crate::observe_day) to receive its steps as
+//! [`Event`]s, without changing the solver.
+
+/// A step of a simulation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Event {
+    /// Day 14: the total load on the north support beams after spin cycle `cycle` (from 1).
+    SpinCycle { cycle: usize, load: usize },
+    /// Day 16: a beam enters the tile at column `x` and row `y` (from 1), going `direction`
+    /// (`^`, `v`, `<` or `>`).
+    Beam { x: usize, y: usize, direction: char },
+    /// Day 20: a pulse is sent from `source` to `destination`. Button presses start with a pulse
+    /// from `button` to `broadcaster`.
+    Pulse {
+        source: &'static str,
+        destination: &'static str,
+        high: bool,
+    },
+    /// Day 21: the number of garden plots reached in exactly `step` steps.
+    Layer { step: usize, plots: usize },
+    /// Day 22: brick `brick` (counting from the lowest) comes to rest on `supported_by` bricks or
+    /// the ground, after falling `fall` cubes.
+    Settled {
+        brick: usize,
+        fall: usize,
+        supported_by: usize,
+    },
+}
+
+/// Receives the events of a simulation as they happen.
+pub trait Observer {
+    fn observe(&mut self, event: &Event);
+}
+
+/// Ignores all events.
+impl Observer for () {
+    fn observe(&mut self, _event: &Event) {}
+}
+
+impl<O: Observer + ?Sized> Observer for &mut O {
+    fn observe(&mut self, event: &Event) {
+        (**self).observe(event);
+    }
+}
+
+/// Both observers receive every event.
+impl<A: Observer, B: Observer> Observer for (A, B) {
+    fn observe(&mut self, event: &Event) {
+        self.0.observe(event);
+        self.1.observe(event);
+    }
+}
+
+/// An observer calling `f` with every event.
+pub fn from_fn<F: FnMut(&Event)>(f: F) -> FromFn<F> {
+    FromFn(f)
+}
+
+pub struct FromFn<F>(F);
+
+impl<F: FnMut(&Event)> Observer for FromFn<F> {
+    fn observe(&mut self, event: &Event) {
+        (self.0)(event);
+    }
+}
+
+/// Remembers the first event for which a condition holds, e.g. the first spin cycle with a load
+/// below some value.
+pub struct First<F> {
+    condition: F,
+    events: usize,
+    found: Option<(usize, Event)>,
+}
+
+impl<F: FnMut(&Event) -> bool> First<F> {
+    pub fn new(condition: F) -> Self {
+        Self {
+            condition,
+            events: 0,
+            found: None,
+        }
+    }
+
+    /// The first event for which the condition holds, with the number of events before it.
+    pub fn found(&self) -> Option<(usize, &Event)> {
+        self.found.as_ref().map(|(index, event)| (*index, event))
+    }
+}
+
+impl<F: FnMut(&Event) -> bool> Observer for First<F> {
+    fn observe(&mut self, event: &Event) {
+        if self.found.is_none() && (self.condition)(event) {
+            self.found = Some((self.events, event.clone()));
+        }
+        self.events += 1;
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn first_finds_the_first_matching_event() {
+    use crate::{get_input, observe_day, Part};
+
+    let mut first =
+        First::new(|event| matches!(event, Event::SpinCycle { load, .. } if *load < 69));
+    let mut cycles = 0;
+    let mut count = from_fn(|event| cycles += matches!(event, Event::SpinCycle { .. }) as usize);
+    let output = observe_day(
+        14,
+        get_input(14, 1).unwrap(),
+        Part::Part2,
+        &mut (&mut first, &mut count),
+    )
+    .unwrap();
+
+    assert_eq!(output.as_deref(), Some("64"));
+    assert_eq!(
+        first.found(),
+        Some((4, &Event::SpinCycle { cycle: 5, load: 65 }))
+    );
+    assert!(cycles > 3);
+}
+
+#[cfg(test)]
+#[test]
+fn examples_observe() {
+    use crate::{for_each_example, observe_day, run_day_part};
+
+    for_each_example(|day, part, input| {
+        let mut events = 0;
+        let output = observe_day(day, input, part.part, &mut from_fn(|_| events += 1))?;
+        if let Some(output) = output {
+            anyhow::ensure!(
+                output == run_day_part(day, part.part, input)?,
+                "observed {output}"
+            );
+            anyhow::ensure!(events > 0, "no events");
+        }
+        Ok(())
+    });
+}
@@ -0,0 +1,46 @@
+//! Parsers shared by days.
+
+use nom::{
+    branch::alt,
+    character::complete::line_ending,
+    combinator::{cut, eof, map, peek, verify},
+    error::context,
+    multi::{many_till, separated_list1},
+    sequence::terminated,
+};
+
+use crate::prelude::IResult;
+
+/// One or more `line`s separated by line endings, up to the end of the input. A line that doesn't
+/// parse completely fails at its position with `expected` as the expected token, instead of ending
+/// the list early and leaving the rest of the input unparsed. Only for the last part of an input,
+/// as nothing may follow the last line.
+pub fn lines<'a, T>(
+    expected: &'static str,
+    line: impl FnMut(&'a str) -> IResult<'a, T>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    separated_list1(
+        line_ending,
+        cut(context(
+            expected,
+            terminated(line, peek(alt((line_ending, eof)))),
+        )),
+    )
+}
+
+/// A row of a grid: one or more `tile`s up to a line ending or the end of the input. Anything else
+/// fails at its position with `expected` as the expected token, so an unknown tile is reported
+/// where it is instead of ending the grid early. Checks of the whole grid, like its dimensions,
+/// only run after all tiles were parsed.
+pub fn tiles<'a, T>(
+    expected: &'static str,
+    tile: impl FnMut(&'a str) -> IResult<'a, T>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    map(
+        verify(
+            many_till(cut(context(expected, tile)), peek(alt((line_ending, eof)))),
+            |(tiles, _): &(Vec<T>, &str)| !tiles.is_empty(),
+        ),
+        |(tiles, _)| tiles,
+    )
+}
@@ -1,13 +1,26 @@
 pub use crate::{
-    bench_day,
+    bench_day, cancel,
     days::DAYS,
-    def,
-    error::Error,
-    get_input, reuse_parsed, run_day, Day,
+    def, dump_parsed,
+    error::{Error, ParseError},
+    fingerprint::{Fingerprint, InputSpecific},
+    generate_input, get_input,
+    observe::{Event, Observer},
+    observe_day,
+    parse::{lines, tiles},
+    render::{Image, Render, Rgb},
+    render_day, reuse_parsed, run_day, run_day_timed, run_day_variant,
+    validate::Violation,
+    validate_day,
+    variant::Variant,
+    variants,
+    visualize::{grid, paint, Color, Visualize},
+    visualize_day, Day, Outcome,
     Part::{self, *},
     Reuse,
 };
 
 pub use anyhow::{anyhow, bail, ensure, Context, Result};
+pub use fastrand::Rng;
 
-pub type IResult<'a, T> = nom::IResult<&'a str, T>;
+pub type IResult<'a, T> = nom::IResult<&'a str, T, nom::error::VerboseError<&'a str>>;
@@ -0,0 +1,66 @@
+//! Property tests: check a fast solution against a brute-force oracle on small random inputs.
+//!
+//! Cases are generated from a seed, so runs are reproducible. Set `PROPERTY_SEED` to run other
+//! cases and `PROPERTY_CASES` to run more (or less) of them.
+
+use std::fmt::Debug;
+
+use anyhow::Result;
+use fastrand::Rng;
+
+const DEFAULT_SEED: u64 = 2023;
+const DEFAULT_CASES: usize = 100;
+
+/// Check `property` on inputs from `generate`. The size given to `generate` grows with each case,
+/// up to `max_size`. When a case fails, the same seed is tried with smaller sizes and the test
+/// panics with the smallest failing input.
+pub fn check<T: Debug>(
+    max_size: usize,
+    generate: impl Fn(&mut Rng, usize) -> T,
+    property: impl Fn(&T) -> Result<()>,
+) {
+    check_cases(DEFAULT_CASES, max_size, generate, property)
+}
+
+/// Like [`check`], but with another number of cases by default, for slow properties.
+pub fn check_cases<T: Debug>(
+    cases: usize,
+    max_size: usize,
+    generate: impl Fn(&mut Rng, usize) -> T,
+    property: impl Fn(&T) -> Result<()>,
+) {
+    let seed = env_or("PROPERTY_SEED", DEFAULT_SEED);
+    let cases = env_or("PROPERTY_CASES", cases as u64) as usize;
+
+    for case in 0..cases {
+        let case_seed = seed.wrapping_add(case as u64);
+        let size = 1 + case * max_size / cases.max(1);
+
+        if let Err(error) = property(&generate(&mut Rng::with_seed(case_seed), size)) {
+            let (size, input, error) = (1..size)
+                .find_map(|size| {
+                    let input = generate(&mut Rng::with_seed(case_seed), size);
+                    property(&input).err().map(|error| (size, input, error))
+                })
+                .unwrap_or_else(|| {
+                    let input = generate(&mut Rng::with_seed(case_seed), size);
+                    (size, input, error)
+                });
+            panic!(
+                "Property failed for seed {case_seed} and size {size}: {error:#}\nInput: {input:#?}"
+            );
+        }
+    }
+}
+
+fn env_or(name: &str, default: u64) -> u64 {
+    std::env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Leak a generated input, because days parse `&'static str`s.
+pub fn leak(input: String) -> &'static str {
+    input.leak()
+}
@@ -0,0 +1,363 @@
+//! Images of grid-shaped or geometric state, written as SVG or PNG with `--render`.
+
+use std::{fmt::Write, fs, path::Path};
+
+use anyhow::{bail, Context, Result};
+
+/// Largest width or height of a PNG image in pixels. SVG images scale by themselves.
+const PNG_SIZE: f64 = 1400.0;
+
+/// State of a day that can be drawn as an image. Days return it from `Day::render`.
+pub trait Render {
+    fn render(&self) -> Image;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const GRAY: Rgb = Rgb(128, 128, 128);
+    pub const RED: Rgb = Rgb(220, 40, 40);
+    pub const GREEN: Rgb = Rgb(40, 160, 60);
+    pub const YELLOW: Rgb = Rgb(240, 200, 40);
+    pub const BLUE: Rgb = Rgb(40, 90, 200);
+
+    /// A colour between `from` (at 0) and `to` (at 1), e.g. for heat maps.
+    pub fn blend(from: Rgb, to: Rgb, t: f64) -> Rgb {
+        let channel =
+            |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * t).round() as u8;
+        Rgb(
+            channel(from.0, to.0),
+            channel(from.1, to.1),
+            channel(from.2, to.2),
+        )
+    }
+
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Shape {
+    Rect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        fill: Rgb,
+    },
+    Polygon {
+        points: Vec<(f64, f64)>,
+        fill: Rgb,
+    },
+    Polyline {
+        points: Vec<(f64, f64)>,
+        stroke: Rgb,
+        width: f64,
+    },
+}
+
+/// Shapes drawn in order on a `width` by `height` canvas. Grids use one unit per tile, with the
+/// tile at column `x` and row `y` covering `(x, y)` to `(x + 1, y + 1)`.
+#[derive(Debug, Clone)]
+pub struct Image {
+    width: f64,
+    height: f64,
+    background: Rgb,
+    shapes: Vec<Shape>,
+}
+
+impl Image {
+    pub fn new(width: f64, height: f64) -> Self {
+        Self {
+            width,
+            height,
+            background: Rgb::WHITE,
+            shapes: Vec::new(),
+        }
+    }
+
+    pub fn rect(&mut self, x: f64, y: f64, width: f64, height: f64, fill: Rgb) -> &mut Self {
+        self.shapes.push(Shape::Rect {
+            x,
+            y,
+            width,
+            height,
+            fill,
+        });
+        self
+    }
+
+    /// Fill the tile at column `x` and row `y` of a grid.
+    pub fn tile(&mut self, x: usize, y: usize, fill: Rgb) -> &mut Self {
+        self.rect(x as f64, y as f64, 1.0, 1.0, fill)
+    }
+
+    pub fn polygon(&mut self, points: Vec<(f64, f64)>, fill: Rgb) -> &mut Self {
+        self.shapes.push(Shape::Polygon { points, fill });
+        self
+    }
+
+    pub fn polyline(&mut self, points: Vec<(f64, f64)>, stroke: Rgb, width: f64) -> &mut Self {
+        self.shapes.push(Shape::Polyline {
+            points,
+            stroke,
+            width,
+        });
+        self
+    }
+
+    /// Write the image to `path`, as SVG or PNG depending on its extension.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let data = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("svg") => self.to_svg().into_bytes(),
+            Some("png") => self.to_png()?,
+            _ => bail!("Cannot render {}, use a .svg or .png file", path.display()),
+        };
+        fs::write(path, data).with_context(|| format!("Cannot write {}", path.display()))
+    }
+
+    pub fn to_svg(&self) -> String {
+        let points = |points: &[(f64, f64)]| {
+            points
+                .iter()
+                .map(|(x, y)| format!("{x},{y}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {} {}" shape-rendering="crispEdges">"#,
+            self.width, self.height
+        );
+        svg.push('\n');
+        let _ = writeln!(
+            svg,
+            r#"<rect width="{}" height="{}" fill="{}"/>"#,
+            self.width,
+            self.height,
+            self.background.hex()
+        );
+        for shape in &self.shapes {
+            let _ = match shape {
+                Shape::Rect {
+                    x,
+                    y,
+                    width,
+                    height,
+                    fill,
+                } => writeln!(
+                    svg,
+                    r#"<rect x="{x}" y="{y}" width="{width}" height="{height}" fill="{}"/>"#,
+                    fill.hex()
+                ),
+                Shape::Polygon { points: p, fill } => writeln!(
+                    svg,
+                    r#"<polygon points="{}" fill="{}"/>"#,
+                    points(p),
+                    fill.hex()
+                ),
+                Shape::Polyline {
+                    points: p,
+                    stroke,
+                    width,
+                } => writeln!(
+                    svg,
+                    r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="{width}" stroke-linejoin="round" stroke-linecap="round" shape-rendering="auto"/>"#,
+                    points(p),
+                    stroke.hex()
+                ),
+            };
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        let raster = Raster::draw(self);
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, raster.width as u32, raster.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(
+            &raster
+                .pixels
+                .iter()
+                .flat_map(|Rgb(r, g, b)| [*r, *g, *b])
+                .collect::<Vec<_>>(),
+        )?;
+        Ok(png)
+    }
+}
+
+/// Pixels of an image for PNG output.
+struct Raster {
+    width: usize,
+    height: usize,
+    scale: f64,
+    pixels: Vec<Rgb>,
+}
+
+impl Raster {
+    fn draw(image: &Image) -> Self {
+        let scale = PNG_SIZE / image.width.max(image.height).max(f64::MIN_POSITIVE);
+        let width = ((image.width * scale).round() as usize).max(1);
+        let height = ((image.height * scale).round() as usize).max(1);
+        let mut raster = Raster {
+            width,
+            height,
+            scale,
+            pixels: vec![image.background; width * height],
+        };
+
+        for shape in &image.shapes {
+            match shape {
+                Shape::Rect {
+                    x,
+                    y,
+                    width,
+                    height,
+                    fill,
+                } => {
+                    let (x0, x1) = (raster.pixel(*x), raster.pixel(x + width));
+                    for py in raster.pixel(*y)..raster.pixel(y + height) {
+                        raster.span(py, x0, x1, *fill);
+                    }
+                }
+                Shape::Polygon { points, fill } => raster.polygon(points, *fill),
+                Shape::Polyline {
+                    points,
+                    stroke,
+                    width,
+                } => raster.polyline(points, *stroke, *width),
+            }
+        }
+
+        raster
+    }
+
+    /// The pixel at image coordinate `value`.
+    fn pixel(&self, value: f64) -> usize {
+        (value * self.scale).round().max(0.0) as usize
+    }
+
+    /// Fill the pixels `x0..x1` of row `py`.
+    fn span(&mut self, py: usize, x0: usize, x1: usize, fill: Rgb) {
+        if py < self.height {
+            let row = py * self.width;
+            self.pixels[row + x0.min(self.width)..row + x1.min(self.width)].fill(fill);
+        }
+    }
+
+    /// Fill a polygon row by row, between pairs of edge crossings.
+    fn polygon(&mut self, points: &[(f64, f64)], fill: Rgb) {
+        for py in 0..self.height {
+            let y = (py as f64 + 0.5) / self.scale;
+            let mut crossings: Vec<f64> = points
+                .iter()
+                .zip(points.iter().cycle().skip(1))
+                .filter(|((_, y0), (_, y1))| (*y0 <= y) != (*y1 <= y))
+                .map(|((x0, y0), (x1, y1))| x0 + (y - y0) * (x1 - x0) / (y1 - y0))
+                .collect();
+            crossings.sort_by(f64::total_cmp);
+            for pair in crossings.chunks_exact(2) {
+                let (x0, x1) = (self.pixel(pair[0]), self.pixel(pair[1]));
+                self.span(py, x0, x1, fill);
+            }
+        }
+    }
+
+    /// Draw lines by stamping squares of the line width along them.
+    fn polyline(&mut self, points: &[(f64, f64)], stroke: Rgb, width: f64) {
+        let size = (width * self.scale).max(1.0);
+        for segment in points.windows(2) {
+            let ((x0, y0), (x1, y1)) = (segment[0], segment[1]);
+            let steps = ((x1 - x0).abs().max((y1 - y0).abs()) * self.scale).ceil() as usize + 1;
+            for step in 0..=steps {
+                let t = step as f64 / steps as f64;
+                let (x, y) = (
+                    (x0 + (x1 - x0) * t) * self.scale,
+                    (y0 + (y1 - y0) * t) * self.scale,
+                );
+                let (left, right) = ((x - size / 2.0).round(), (x + size / 2.0).round());
+                for py in
+                    (y - size / 2.0).round().max(0.0) as usize..(y + size / 2.0).round() as usize
+                {
+                    self.span(py, left.max(0.0) as usize, right.max(0.0) as usize, stroke);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn png_fills_tiles_and_polygons() {
+    let mut image = Image::new(2.0, 2.0);
+    image.tile(1, 0, Rgb::RED).polygon(
+        vec![(0.0, 1.0), (1.0, 1.0), (1.0, 2.0), (0.0, 2.0)],
+        Rgb::BLUE,
+    );
+    let raster = Raster::draw(&image);
+    let at = |x: usize, y: usize| raster.pixels[y * raster.width + x];
+
+    let half = raster.width / 2;
+    assert_eq!(at(0, 0), Rgb::WHITE);
+    assert_eq!(at(half + 1, 0), Rgb::RED);
+    assert_eq!(at(0, half + 1), Rgb::BLUE);
+    assert_eq!(at(half + 1, half + 1), Rgb::WHITE);
+
+    let png = image.to_png().unwrap();
+    assert_eq!(&png[1..4], b"PNG");
+    assert!(image
+        .to_svg()
+        .contains(r##"<rect x="1" y="0" width="1" height="1" fill="#dc2828"/>"##));
+}
+
+#[cfg(test)]
+#[test]
+fn examples_render() {
+    use crate::{def, for_each_example, get_input, render_day, Part};
+
+    for_each_example(|day, part, input| {
+        render_day(day, input, part.part).map(|image| image.map(|image| image.to_svg()))?;
+        Ok(())
+    });
+
+    // The heat loss along the drawn route of day 17 is the answer
+    let input = get_input(17, 1).unwrap();
+    let heat_loss: Vec<Vec<u32>> = input
+        .lines()
+        .map(|line| line.chars().map(|c| c.to_digit(10).unwrap()).collect())
+        .collect();
+    let image = render_day(17, input, Part::Part1).unwrap().unwrap();
+    let route = image
+        .shapes
+        .iter()
+        .find_map(|shape| match shape {
+            Shape::Polyline { points, .. } => Some(points),
+            _ => None,
+        })
+        .unwrap();
+    let tile = |(x, y): (f64, f64)| (x.floor() as i64, y.floor() as i64);
+    assert_eq!(tile(route[0]), (0, 0));
+    assert_eq!(
+        tile(route[route.len() - 1]),
+        (heat_loss[0].len() as i64 - 1, heat_loss.len() as i64 - 1)
+    );
+    let mut total = 0;
+    for turn in route.windows(2) {
+        let ((x1, y1), (x2, y2)) = (tile(turn[0]), tile(turn[1]));
+        assert!(x1 == x2 || y1 == y2, "The crucible only moves straight");
+        let (dx, dy) = ((x2 - x1).signum(), (y2 - y1).signum());
+        let (mut x, mut y) = (x1, y1);
+        while (x, y) != (x2, y2) {
+            (x, y) = (x + dx, y + dy);
+            total += heat_loss[y as usize][x as usize];
+        }
+    }
+    assert_eq!(total.to_string(), def::expected(17, 1, Part::Part1));
+}
@@ -0,0 +1,70 @@
+/// Fit `time = c * size^k` to `(size, time)` samples with least squares on a log-log scale, and
+/// return the exponent `k`. Samples without a positive size or time are ignored. At least two
+/// different sizes are needed.
+pub fn fit_exponent(samples: &[(f64, f64)]) -> Option<f64> {
+    let points: Vec<_> = samples
+        .iter()
+        .filter(|(size, time)| *size > 0.0 && *time > 0.0)
+        .map(|(size, time)| (size.ln(), time.ln()))
+        .collect();
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+#[cfg(test)]
+#[test]
+fn fit_exponent_of_quadratic_samples() {
+    let samples: Vec<_> = [1.0, 2.0, 4.0, 8.0]
+        .into_iter()
+        .map(|size: f64| (size, 3.0 * size.powi(2)))
+        .collect();
+    assert!((fit_exponent(&samples).unwrap() - 2.0).abs() < 1e-9);
+    assert_eq!(fit_exponent(&[(4.0, 1.0), (4.0, 2.0)]), None);
+}
+
+#[cfg(test)]
+#[test]
+fn generated_inputs_are_valid() {
+    use crate::{error::Error, prelude::*, run_day_part};
+
+    for day in DAYS {
+        for size in [1, 2, 3, 4] {
+            let Some(input) =
+                generate_input(day.day, &mut Rng::with_seed(size as u64), size).unwrap()
+            else {
+                continue;
+            };
+            let input = crate::property::leak(input);
+
+            let violations = validate_day(day.day, input)
+                .unwrap_or_else(|error| panic!("Day {} size {size}: {error:#}\n{input}", day.day));
+            assert!(
+                violations.is_empty(),
+                "Day {} size {size}: {violations:?}\n{input}",
+                day.day
+            );
+
+            for part in [Part1, Part2] {
+                if let Err(error) = run_day_part(day.day, part, input) {
+                    assert!(
+                        matches!(
+                            error.downcast_ref::<Error>(),
+                            Some(Error::InputSpecific { .. } | Error::PartNotImplemented(_))
+                        ),
+                        "Day {} {part:?} size {size}: {error:#}\n{input}",
+                        day.day
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,352 @@
+//! Judging submitted answers and remembering them, for the `submit` subcommand. Answers known to
+//! be wrong, or outside the bounds of earlier too high or too low answers, are refused before
+//! they are submitted.
+
+use std::{
+    fmt,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config,
+    fetch::{self, YEAR},
+};
+
+/// The verdict on a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "verdict", rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Submitted too soon after the previous answer, retry after `wait_seconds`.
+    RateLimited {
+        wait_seconds: Option<u64>,
+    },
+    /// The part is already solved, or not unlocked yet.
+    WrongLevel,
+    /// A response that [`Verdict::parse`] doesn't know, with the start of its text. The answer may
+    /// have been judged, so it isn't submitted again.
+    Unrecognized {
+        response: String,
+    },
+}
+
+impl Verdict {
+    /// The verdict in the page returned for a submitted answer.
+    pub fn parse(page: &str) -> Result<Verdict> {
+        Ok(if page.contains("That's the right answer") {
+            Verdict::Correct
+        } else if page.contains("That's not the right answer") {
+            if page.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if page.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else if page.contains("You gave an answer too recently") {
+            Verdict::RateLimited {
+                wait_seconds: wait_seconds(page),
+            }
+        } else if page.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            bail!("Unrecognized response to the submitted answer")
+        })
+    }
+
+    /// The verdict to log for a response that can't be parsed: the start of the text of the
+    /// article with the verdict, or else of the whole page.
+    pub fn unrecognized(page: &str) -> Verdict {
+        let article = page.find("<article").map_or(page, |start| &page[start..]);
+        let mut text = String::new();
+        let mut in_tag = false;
+        for c in article.chars() {
+            match c {
+                '<' => in_tag = true,
+                '>' => in_tag = false,
+                _ if !in_tag => text.push(c),
+                _ => {}
+            }
+        }
+        Verdict::Unrecognized {
+            response: text
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .chars()
+                .take(200)
+                .collect(),
+        }
+    }
+
+    /// Whether the answer was judged, so it tells something about other answers.
+    fn judged(&self) -> bool {
+        !matches!(
+            self,
+            Verdict::RateLimited { .. } | Verdict::WrongLevel | Verdict::Unrecognized { .. }
+        )
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::RateLimited {
+                wait_seconds: Some(seconds),
+            } => write!(f, "submitted too recently, wait {seconds}s"),
+            Verdict::RateLimited { wait_seconds: None } => write!(f, "submitted too recently"),
+            Verdict::WrongLevel => write!(f, "already solved or not unlocked"),
+            Verdict::Unrecognized { response } => write!(f, "unrecognized response \"{response}\""),
+        }
+    }
+}
+
+/// Parse the wait time of "You have 1m 5s left to wait".
+fn wait_seconds(page: &str) -> Option<u64> {
+    let start = page.find("You have ")? + "You have ".len();
+    let end = start + page[start..].find(" left to wait")?;
+    page[start..end]
+        .split_whitespace()
+        .map(|amount| {
+            let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+            let number: u64 = number.parse().ok()?;
+            match unit {
+                "h" => Some(number * 3600),
+                "m" => Some(number * 60),
+                "s" => Some(number),
+                _ => None,
+            }
+        })
+        .sum()
+}
+
+/// A submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Guess {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    #[serde(flatten)]
+    pub verdict: Verdict,
+    /// RFC 3339 time of submission
+    pub time: String,
+}
+
+/// Log of all submitted answers, one JSON object per line.
+pub struct GuessLog {
+    path: PathBuf,
+    guesses: Vec<Guess>,
+}
+
+impl GuessLog {
+    /// Open the log at `path`. A missing log is empty.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let guesses = match fs::read_to_string(&path) {
+            Ok(log) => log
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(serde_json::from_str)
+                .collect::<Result<_, _>>()?,
+            Err(_) => Vec::new(),
+        };
+        Ok(Self { path, guesses })
+    }
+
+    /// Default location of the log of the server at `base_url`, in the data directory so the
+    /// guesses survive `cargo clean` and are shared by all build profiles. Guesses of different
+    /// servers are kept apart, like the downloads of [`fetch::Fetcher`].
+    pub fn default_path(base_url: &str) -> Result<PathBuf> {
+        let dir = config::data_dir().context("Cannot find the home directory")?;
+        Ok(dir
+            .join(fetch::server_dir(base_url))
+            .join(format!("{YEAR}-guesses.jsonl")))
+    }
+
+    /// The earlier guesses for a part of a day.
+    pub fn guesses(&self, day: u8, part: u8) -> impl Iterator<Item = &Guess> {
+        self.guesses
+            .iter()
+            .filter(move |guess| guess.day == day && guess.part == part)
+    }
+
+    /// Fail if `answer` is known to be wrong: it was judged before, the part is solved, or it is
+    /// not below a too high answer or not above a too low answer.
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Result<()> {
+        let number = answer.parse::<i128>().ok();
+        if let Some(guess) = self.guesses(day, part).find(|guess| {
+            guess.answer == answer && matches!(guess.verdict, Verdict::Unrecognized { .. })
+        }) {
+            bail!("{answer} was submitted before with an {}", guess.verdict);
+        }
+        for guess in self
+            .guesses(day, part)
+            .filter(|guess| guess.verdict.judged())
+        {
+            let bound = guess.answer.parse::<i128>().ok();
+            match (&guess.verdict, number, bound) {
+                (Verdict::Correct, ..) if guess.answer == answer => {
+                    bail!("{answer} is already known to be correct")
+                }
+                (Verdict::Correct, ..) => {
+                    bail!("Already solved, the correct answer is {}", guess.answer)
+                }
+                (verdict, ..) if guess.answer == answer => {
+                    bail!("{answer} was submitted before and is {verdict}")
+                }
+                (Verdict::TooHigh, Some(number), Some(bound)) if number >= bound => {
+                    bail!("{answer} is too high, {bound} already is")
+                }
+                (Verdict::TooLow, Some(number), Some(bound)) if number <= bound => {
+                    bail!("{answer} is too low, {bound} already is")
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Add a guess to the log, and append it to the file.
+    pub fn record(&mut self, guess: Guess) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(&guess)?)?;
+        self.guesses.push(guess);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn verdicts_are_parsed() {
+    let page = |text: &str| format!("<main>\n<article><p>{text}</p></article>\n</main>");
+
+    assert_eq!(
+        Verdict::parse(&page(
+            "That's the right answer! You are one gold star closer."
+        ))
+        .unwrap(),
+        Verdict::Correct
+    );
+    assert_eq!(
+        Verdict::parse(&page(
+            "That's not the right answer; your answer is too high."
+        ))
+        .unwrap(),
+        Verdict::TooHigh
+    );
+    assert_eq!(
+        Verdict::parse(&page(
+            "That's not the right answer; your answer is too low."
+        ))
+        .unwrap(),
+        Verdict::TooLow
+    );
+    assert_eq!(
+        Verdict::parse(&page("That's not the right answer. If you're stuck, ...")).unwrap(),
+        Verdict::Wrong
+    );
+    assert_eq!(
+        Verdict::parse(&page(
+            "You gave an answer too recently. You have 1m 5s left to wait."
+        ))
+        .unwrap(),
+        Verdict::RateLimited {
+            wait_seconds: Some(65)
+        }
+    );
+    assert_eq!(
+        Verdict::parse(&page("You don't seem to be solving the right level.")).unwrap(),
+        Verdict::WrongLevel
+    );
+    assert!(Verdict::parse(&page("Hello")).is_err());
+}
+
+#[cfg(test)]
+#[test]
+fn known_wrong_answers_are_refused() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir
+        .path()
+        .join("adventofcode.com")
+        .join("2023-guesses.jsonl");
+    let mut log = GuessLog::open(&path).unwrap();
+    let guess = |answer: &str, verdict| Guess {
+        day: 1,
+        part: 2,
+        answer: answer.to_string(),
+        verdict,
+        time: String::new(),
+    };
+    log.record(guess("100", Verdict::TooHigh)).unwrap();
+    log.record(guess("10", Verdict::TooLow)).unwrap();
+    log.record(guess("42", Verdict::Wrong)).unwrap();
+    log.record(guess("50", Verdict::RateLimited { wait_seconds: None }))
+        .unwrap();
+    log.record(guess(
+        "60",
+        Verdict::unrecognized("<main><article><p>Hm?</p></article></main>"),
+    ))
+    .unwrap();
+
+    let log = GuessLog::open(&path).unwrap();
+    assert_eq!(log.guesses(1, 2).count(), 5);
+    assert_eq!(
+        log.guesses(1, 2).last().unwrap().verdict,
+        Verdict::Unrecognized {
+            response: "Hm?".to_string()
+        }
+    );
+
+    for refused in ["100", "101", "10", "-3", "42", "60"] {
+        assert!(log.check(1, 2, refused).is_err(), "{refused}");
+    }
+    for allowed in ["11", "50", "99"] {
+        assert!(log.check(1, 2, allowed).is_ok(), "{allowed}");
+    }
+    assert!(log.check(1, 1, "100").is_ok());
+}
+
+#[cfg(test)]
+#[test]
+fn answers_are_posted() {
+    use crate::fetch::{mock_server, Fetcher};
+
+    let body = "<article><p>That's the right answer!</p></article>";
+    let (base_url, requests) = mock_server(vec![format!(
+        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )]);
+    let cache_dir = tempfile::tempdir().unwrap();
+    let fetcher = Fetcher {
+        base_url,
+        session: "secret".to_string(),
+        cache_dir: cache_dir.path().to_path_buf(),
+        rate_limit: std::time::Duration::ZERO,
+    };
+
+    let page = fetcher.answer(7, 2, "6440").unwrap();
+    assert_eq!(Verdict::parse(&page).unwrap(), Verdict::Correct);
+
+    let request = requests.recv().unwrap();
+    assert!(request.starts_with("POST /2023/day/7/answer "), "{request}");
+    assert!(request.contains("Cookie: session=secret"), "{request}");
+    assert!(request.ends_with("level=2&answer=6440"), "{request}");
+}
@@ -0,0 +1,88 @@
+//! Structured tracing of the parsing and solving of days, shown with `--trace`.
+//!
+//! Every run of a day is a `day` span, with `parse`, `part1` and `part2` spans inside. Solvers
+//! emit events from inside these spans, e.g. the pulses of day 20 at trace level.
+
+use std::io;
+
+use anyhow::{anyhow, Result};
+use tracing::Subscriber;
+use tracing_subscriber::{fmt::format::FmtSpan, fmt::MakeWriter, EnvFilter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceFormat {
+    /// Human readable lines
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+/// A subscriber writing the spans and events enabled by `filter` to `writer`. The filter is a
+/// level like `debug`, or a list of directives like `aoc2023::days::day20=trace`.
+pub fn subscriber<W>(
+    filter: &str,
+    format: TraceFormat,
+    writer: W,
+) -> Result<Box<dyn Subscriber + Send + Sync>>
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let filter =
+        EnvFilter::try_new(filter).map_err(|error| anyhow!("Invalid trace filter: {error}"))?;
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(writer);
+
+    Ok(match format {
+        TraceFormat::Text => Box::new(builder.finish()),
+        TraceFormat::Json => Box::new(builder.json().finish()),
+    })
+}
+
+/// Trace the whole process to stderr.
+pub fn init(filter: &str, format: TraceFormat) -> Result<()> {
+    tracing::subscriber::set_global_default(subscriber(filter, format, io::stderr)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn spans_and_events_are_traced_as_json() {
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let buffer = Buffer::default();
+    let writer = buffer.clone();
+    let subscriber = subscriber("trace", TraceFormat::Json, move || writer.clone()).unwrap();
+    tracing::subscriber::with_default(subscriber, || {
+        crate::run_day(20, crate::get_input(20, 1).unwrap(), true, false).unwrap();
+    });
+
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    let lines = output
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .collect::<Vec<_>>();
+    let closed = |name: &str| {
+        lines
+            .iter()
+            .any(|line| line["span"]["name"] == name && line["fields"]["message"] == "close")
+    };
+    assert!(closed("day") && closed("parse") && closed("part1"));
+    assert!(lines
+        .iter()
+        .any(|line| line["fields"]["message"] == "pulse" && line["fields"]["pulse"] == "high"));
+}
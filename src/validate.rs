@@ -0,0 +1,34 @@
+use std::fmt;
+
+use crate::Part;
+
+/// An unstated property of the puzzle input a solution relies on, which doesn't hold for the input
+/// at hand. The answer of that part is probably wrong.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub part: Part,
+    pub assumption: &'static str,
+    pub details: String,
+}
+
+impl Violation {
+    pub fn new(part: Part, assumption: &'static str, details: impl Into<String>) -> Self {
+        Self {
+            part,
+            assumption,
+            details: details.into(),
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Part {}: {}: {}",
+            u8::from(self.part),
+            self.assumption,
+            self.details
+        )
+    }
+}
@@ -0,0 +1,31 @@
+use crate::Part;
+
+/// Name of the solution given by `Day::part1` and `Day::part2`.
+pub const DEFAULT: &str = "default";
+
+/// An alternative solution of a part, e.g. a brute force approach the real solution is checked
+/// against. Days register them in `Day::VARIANTS` and they are selected with `--variant`.
+pub struct Variant<P, O> {
+    pub name: &'static str,
+    pub part: Part,
+    pub solve: fn(&P) -> anyhow::Result<O>,
+}
+
+#[cfg(test)]
+#[test]
+fn variants_agree_on_examples() {
+    use crate::{for_each_example, run_day_variant, variants};
+
+    for_each_example(|day, part, input| {
+        for (_, name) in variants(day)?.iter().filter(|(p, _)| *p == part.part) {
+            let output = run_day_variant(day, part.part, name, input)
+                .unwrap_or_else(|error| format!("Error: {error:#}"));
+            anyhow::ensure!(
+                part.expected == output,
+                "variant {name} gives {output}, expected {}",
+                part.expected
+            );
+        }
+        Ok(())
+    });
+}
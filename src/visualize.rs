@@ -0,0 +1,67 @@
+//! Rendering of parsed or solved state as ANSI-coloured terminal text, shown with `--visualize`.
+
+use std::fmt::Display;
+
+/// State of a day that can be shown in the terminal, e.g. a grid with the tiles that are part of
+/// the answer highlighted. Days return it from `Day::visualize`.
+pub trait Visualize {
+    /// Render as lines of text with ANSI colour codes.
+    fn visualize(&self) -> String;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red = 31,
+    Green = 32,
+    Yellow = 33,
+    Blue = 34,
+    Magenta = 35,
+    Cyan = 36,
+    Gray = 90,
+}
+
+/// `text` in the given colour.
+pub fn paint(text: impl Display, color: Color) -> String {
+    format!("\x1b[{}m{text}\x1b[0m", color as u8)
+}
+
+/// Render a grid of `width` by `height` tiles row by row, with `tile` rendering the tile at
+/// column `x` and row `y`.
+pub fn grid(width: usize, height: usize, mut tile: impl FnMut(usize, usize) -> String) -> String {
+    (0..height)
+        .map(|y| (0..width).map(|x| tile(x, y)).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+#[test]
+fn grid_paints_tiles_row_by_row() {
+    let rendered = grid(2, 2, |x, y| match (x, y) {
+        (0, 0) => paint('#', Color::Red),
+        _ => ".".to_string(),
+    });
+    assert_eq!(rendered, "\x1b[31m#\x1b[0m.\n..");
+}
+
+#[cfg(test)]
+#[test]
+fn examples_visualize() {
+    use crate::{def, for_each_example, get_input, visualize_day, Part};
+
+    for_each_example(|day, part, input| visualize_day(day, input, part.part).map(|_| ()));
+
+    // All energized tiles are highlighted, in yellow for empty space and red for the others
+    let energized = visualize_day(16, get_input(16, 1).unwrap(), Part::Part1)
+        .unwrap()
+        .unwrap();
+    let highlighted = energized.matches("\x1b[33m").count() + energized.matches("\x1b[31m").count();
+    assert_eq!(highlighted.to_string(), def::expected(16, 1, Part::Part1));
+
+    let garden = visualize_day(21, get_input(21, 1).unwrap(), Part::Part1)
+        .unwrap()
+        .unwrap();
+    // The start is shown as S, but is reached again after an even number of steps
+    let reached = garden.matches(&paint('O', Color::Green)).count() + 1;
+    assert_eq!(reached.to_string(), def::expected(21, 1, Part::Part1));
+}
@@ -0,0 +1,101 @@
+//! Detecting changes to the sources and inputs of days, for the `watch` subcommand.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use anyhow::{Context, Result};
+
+/// The directory with the sources and inputs of a day in this repository.
+pub fn day_dir(day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("days")
+        .join(format!("day{day:02}"))
+}
+
+/// The file of the input (0) or an example of a day in this repository.
+pub fn input_path(day: u8, example: usize) -> PathBuf {
+    let name = match example {
+        0 => "input.txt".to_string(),
+        _ => format!("example{example}.txt"),
+    };
+    day_dir(day).join("inputs").join(name)
+}
+
+/// Modification time and size of every file below some directories.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Snapshot(BTreeMap<PathBuf, (SystemTime, u64)>);
+
+impl Snapshot {
+    pub fn take(dirs: &[PathBuf]) -> Result<Self> {
+        let mut snapshot = Snapshot::default();
+        for dir in dirs {
+            snapshot.add(dir)?;
+        }
+        Ok(snapshot)
+    }
+
+    fn add(&mut self, dir: &Path) -> Result<()> {
+        for entry in fs::read_dir(dir).with_context(|| format!("Cannot read {}", dir.display()))? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if metadata.is_dir() {
+                self.add(&entry.path())?;
+            } else {
+                self.0
+                    .insert(entry.path(), (metadata.modified()?, metadata.len()));
+            }
+        }
+        Ok(())
+    }
+
+    /// The files that were added, changed or removed in `newer`.
+    pub fn changes(&self, newer: &Snapshot) -> Vec<PathBuf> {
+        let mut changes: Vec<_> = newer
+            .0
+            .iter()
+            .filter(|(path, stamp)| self.0.get(*path) != Some(stamp))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changes.extend(
+            self.0
+                .keys()
+                .filter(|path| !newer.0.contains_key(*path))
+                .cloned(),
+        );
+        changes
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn snapshot_detects_changes() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let dir = tempdir.path().to_path_buf();
+    fs::create_dir_all(dir.join("inputs")).unwrap();
+    fs::write(dir.join("mod.rs"), "mod inputs;").unwrap();
+    fs::write(dir.join("inputs").join("example1.txt"), "1").unwrap();
+
+    let take = || Snapshot::take(std::slice::from_ref(&dir)).unwrap();
+    let before = take();
+    assert!(before.changes(&take()).is_empty());
+
+    fs::write(dir.join("inputs").join("example1.txt"), "12").unwrap();
+    fs::write(dir.join("inputs").join("example2.txt"), "2").unwrap();
+    fs::remove_file(dir.join("mod.rs")).unwrap();
+    let mut changes = before.changes(&take());
+    changes.sort();
+
+    assert_eq!(
+        changes,
+        [
+            dir.join("inputs").join("example1.txt"),
+            dir.join("inputs").join("example2.txt"),
+            dir.join("mod.rs"),
+        ]
+    );
+}